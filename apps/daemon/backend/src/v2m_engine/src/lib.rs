@@ -32,6 +32,17 @@ use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 type RingProducer = ringbuf::HeapProd<f32>;
 type RingConsumer = ringbuf::HeapCons<f32>;
 
+/// Extrae hasta `max` muestras del consumidor con una sola operación `pop_slice`.
+///
+/// Evita el costo de un `try_pop` (una operación atómica) por muestra, que en
+/// grabaciones largas suma cientos de miles de operaciones al detener.
+fn pop_samples(consumer: &mut RingConsumer, max: usize) -> Vec<f32> {
+    let mut data = vec![0.0f32; max.min(consumer.occupied_len())];
+    let popped = consumer.pop_slice(&mut data);
+    data.truncate(popped);
+    data
+}
+
 /// Comandos para el canal lock-free de control del AudioRecorder.
 #[derive(Debug, Clone)]
 enum AudioCommand {
@@ -41,6 +52,196 @@ enum AudioCommand {
     Stopped,
 }
 
+/// Preset de calidad del re-muestreador sinc.
+///
+/// Cada preset fija el largo del kernel sinc, la frecuencia de corte y la ventana.
+/// Kernels más largos atenúan mejor el aliasing (p. ej. 44100→16000Hz) a costa de
+/// más CPU por muestra; `Fast` cuesta aprox. 4x menos que `High`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResampleQuality {
+    /// sinc_len=64, corte 0.90, ventana Hann2. Latencia y CPU mínimas.
+    Fast,
+    /// sinc_len=128, corte 0.925, ventana Blackman2.
+    Balanced,
+    /// sinc_len=256, corte 0.95, ventana BlackmanHarris2 (valor por defecto).
+    High,
+}
+
+impl ResampleQuality {
+    fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "fast" => Ok(ResampleQuality::Fast),
+            "balanced" => Ok(ResampleQuality::Balanced),
+            "high" => Ok(ResampleQuality::High),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(
+                "resample_quality debe ser 'fast', 'balanced' o 'high'",
+            )),
+        }
+    }
+
+    fn params(self) -> SincInterpolationParameters {
+        let (sinc_len, f_cutoff, window) = match self {
+            ResampleQuality::Fast => (64, 0.90, WindowFunction::Hann2),
+            ResampleQuality::Balanced => (128, 0.925, WindowFunction::Blackman2),
+            ResampleQuality::High => (256, 0.95, WindowFunction::BlackmanHarris2),
+        };
+
+        SincInterpolationParameters {
+            sinc_len,
+            f_cutoff,
+            interpolation: SincInterpolationType::Linear,
+            oversampling_factor: 256,
+            window,
+        }
+    }
+}
+
+/// Promedia los canales de audio intercalado en una señal mono.
+///
+/// Las muestras finales que no completan un frame se descartan.
+fn downmix_to_mono(interleaved: Vec<f32>, channels: u16) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved;
+    }
+    let channels = channels as usize;
+    interleaved
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Re-muestrea un bloque mono completo de `from_rate` a `to_rate`.
+fn resample_block(
+    raw_data: Vec<f32>,
+    from_rate: u32,
+    to_rate: u32,
+    quality: ResampleQuality,
+) -> PyResult<Vec<f32>> {
+    let f_ratio = to_rate as f64 / from_rate as f64;
+    let mut resampler = SincFixedIn::<f32>::new(
+        f_ratio,
+        256.0,
+        quality.params(),
+        raw_data.len(),
+        1, // canales
+    )
+    .map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!("Fallo init re-muestreador: {}", e))
+    })?;
+
+    let waves = vec![raw_data];
+    let mut resampled_waves = resampler.process(&waves, None).map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!("Fallo al re-muestrear: {}", e))
+    })?;
+
+    Ok(resampled_waves.swap_remove(0))
+}
+
+/// Tamaño de bloque (a la tasa del dispositivo) con el que se alimenta el re-muestreador incremental.
+const STREAM_RESAMPLE_CHUNK: usize = 1024;
+
+/// Re-muestreador incremental que conserva estado entre chunks.
+///
+/// Alimenta a `SincFixedIn` en bloques fijos y guarda el remanente para la
+/// siguiente llamada, de modo que el filtro mantiene su historia y no aparecen
+/// transitorios en los bordes de cada chunk. Al cerrar el flujo, `flush()`
+/// completa la salida hasta la longitud exacta `entrada * ratio`.
+struct StreamResampler {
+    resampler: SincFixedIn<f32>,
+    pending: Vec<f32>,
+    ratio: f64,
+    total_in: usize,
+    total_out: usize,
+}
+
+impl StreamResampler {
+    fn new(from_rate: u32, to_rate: u32, quality: ResampleQuality) -> PyResult<Self> {
+        let ratio = to_rate as f64 / from_rate as f64;
+        let resampler = SincFixedIn::<f32>::new(
+            ratio,
+            1.0,
+            quality.params(),
+            STREAM_RESAMPLE_CHUNK,
+            1, // canales
+        )
+        .map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Fallo init re-muestreador: {}", e))
+        })?;
+
+        Ok(Self {
+            resampler,
+            pending: Vec::with_capacity(STREAM_RESAMPLE_CHUNK),
+            ratio,
+            total_in: 0,
+            total_out: 0,
+        })
+    }
+
+    /// Re-muestrea todos los bloques completos disponibles; el resto queda pendiente.
+    fn process(&mut self, samples: &[f32]) -> PyResult<Vec<f32>> {
+        self.pending.extend_from_slice(samples);
+
+        let mut out = Vec::new();
+        let mut offset = 0;
+        while self.pending.len() - offset >= STREAM_RESAMPLE_CHUNK {
+            let block = &self.pending[offset..offset + STREAM_RESAMPLE_CHUNK];
+            let waves = self.resampler.process(&[block], None).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Fallo al re-muestrear: {}", e))
+            })?;
+            offset += STREAM_RESAMPLE_CHUNK;
+            self.total_in += STREAM_RESAMPLE_CHUNK;
+            self.emit(&waves[0], usize::MAX, &mut out);
+        }
+        self.pending.drain(..offset);
+
+        Ok(out)
+    }
+
+    /// Procesa el remanente y vacía el retardo del filtro al final del flujo.
+    fn flush(&mut self) -> PyResult<Vec<f32>> {
+        let mut out = Vec::new();
+        let map_err = |e: rubato::ResampleError| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Fallo al re-muestrear: {}", e))
+        };
+
+        if !self.pending.is_empty() {
+            let waves = self
+                .resampler
+                .process_partial(Some(&[self.pending.as_slice()]), None)
+                .map_err(map_err)?;
+            self.total_in += self.pending.len();
+            self.pending.clear();
+            self.emit(&waves[0], self.expected_out(), &mut out);
+        }
+
+        while self.total_out < self.expected_out() {
+            let waves = self.resampler.process_partial::<&[f32]>(None, None).map_err(map_err)?;
+            if waves[0].is_empty() {
+                break;
+            }
+            self.emit(&waves[0], self.expected_out(), &mut out);
+        }
+
+        Ok(out)
+    }
+
+    /// Muestras de entrada que faltan para completar el siguiente bloque interno.
+    fn samples_to_next_block(&self) -> usize {
+        STREAM_RESAMPLE_CHUNK - self.pending.len()
+    }
+
+    fn expected_out(&self) -> usize {
+        (self.total_in as f64 * self.ratio).round() as usize
+    }
+
+    /// Agrega `chunk` a `out` sin superar `limit` muestras de salida totales.
+    fn emit(&mut self, chunk: &[f32], limit: usize, out: &mut Vec<f32>) {
+        let take = chunk.len().min(limit.saturating_sub(self.total_out));
+        out.extend_from_slice(&chunk[..take]);
+        self.total_out += take;
+    }
+}
+
 /// Implementación de AudioRecorder en Rust usando Búfer Circular Lock-Free.
 ///
 /// Utiliza CPAL para captura de audio multiplataforma y Rubato para re-muestreo
@@ -54,25 +255,34 @@ struct AudioRecorder {
     requested_sample_rate: u32,
     device_sample_rate: u32,
     channels: u16,
+    resample_quality: ResampleQuality,
     is_recording: bool,
+    /// Muestras descartadas por el productor al encontrar el búfer lleno
+    dropped_samples: Arc<AtomicUsize>,
 }
 
 #[pymethods]
 impl AudioRecorder {
+    /// Args:
+    ///     sample_rate: Tasa objetivo en Hz.
+    ///     channels: Número de canales a capturar.
+    ///     resample_quality: Preset del re-muestreador: "fast", "balanced" o "high".
     #[new]
-    #[pyo3(signature = (sample_rate=16000, channels=1))]
-    fn new(sample_rate: u32, channels: u16) -> Self {
+    #[pyo3(signature = (sample_rate=16000, channels=1, resample_quality="high"))]
+    fn new(sample_rate: u32, channels: u16, resample_quality: &str) -> PyResult<Self> {
         let _ = pyo3_log::try_init();
 
-        AudioRecorder {
+        Ok(AudioRecorder {
             stream: None,
             consumer: Arc::new(Mutex::new(None)),
             notify: Arc::new(Notify::new()),
             requested_sample_rate: sample_rate,
             device_sample_rate: 0,
             channels,
+            resample_quality: ResampleQuality::parse(resample_quality)?,
             is_recording: false,
-        }
+            dropped_samples: Arc::new(AtomicUsize::new(0)),
+        })
     }
 
     fn start(&mut self) -> PyResult<()> {
@@ -119,6 +329,15 @@ impl AudioRecorder {
                         c.max_sample_rate()
                     };
 
+                // La tasa se vuelve a consultar en cada sesión: el dispositivo por defecto
+                // puede haber cambiado desde el último start()
+                if self.device_sample_rate != 0 && self.device_sample_rate != target_rate.0 {
+                    warn!(
+                        "Tasa del dispositivo cambió entre sesiones: {}Hz -> {}Hz",
+                        self.device_sample_rate, target_rate.0
+                    );
+                }
+
                 self.device_sample_rate = target_rate.0;
                 c.with_sample_rate(target_rate).into()
             }
@@ -142,6 +361,8 @@ impl AudioRecorder {
 
         *self.consumer.lock().unwrap() = Some(consumer);
         let notify = self.notify.clone();
+        self.dropped_samples.store(0, Ordering::Relaxed);
+        let dropped_samples = self.dropped_samples.clone();
 
         let err_fn = move |err| {
             error!("Error en flujo de audio: {}", err);
@@ -151,8 +372,11 @@ impl AudioRecorder {
             .build_input_stream(
                 &config,
                 move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    // API ringbuf 0.4: push_slice devuelve conteo, lo ignoramos (descarta muestras si está lleno)
-                    let _ = producer.push_slice(data);
+                    // API ringbuf 0.4: push_slice devuelve conteo; lo que no cabe se descarta y se contabiliza
+                    let pushed = producer.push_slice(data);
+                    if pushed < data.len() {
+                        dropped_samples.fetch_add(data.len() - pushed, Ordering::Relaxed);
+                    }
                     notify.notify_one();
                 },
                 err_fn,
@@ -179,15 +403,49 @@ impl AudioRecorder {
     fn read_chunk<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray1<f32>> {
         let mut guard = self.consumer.lock().unwrap();
         if let Some(consumer) = guard.as_mut() {
-             let mut data = Vec::new();
-             while let Some(s) = consumer.try_pop() {
-                 data.push(s);
-             }
-             return Ok(PyArray1::from_vec(py, data));
+            return Ok(PyArray1::from_vec(py, pop_samples(consumer, usize::MAX)));
+        }
+        Ok(PyArray1::from_vec(py, Vec::new()))
+    }
+
+    /// Lee como máximo `n` muestras del búfer, dejando el resto para la siguiente lectura.
+    ///
+    /// Permite lecturas alineadas a frame (p. ej. exactamente 480 muestras para el VAD)
+    /// sin re-bufferizar en Python. Puede devolver menos de `n` si no hay suficientes datos.
+    fn read_chunk_n<'py>(&self, py: Python<'py>, n: usize) -> PyResult<&'py PyArray1<f32>> {
+        let mut guard = self.consumer.lock().unwrap();
+        if let Some(consumer) = guard.as_mut() {
+            return Ok(PyArray1::from_vec(py, pop_samples(consumer, n)));
         }
         Ok(PyArray1::from_vec(py, Vec::new()))
     }
 
+    /// Muestras pendientes de leer en el búfer circular (0 si no hay sesión).
+    ///
+    /// Comparado con `buffer_capacity()` permite a un consumidor lento reaccionar
+    /// antes de que el productor empiece a descartar muestras.
+    fn occupied_samples(&self) -> usize {
+        self.consumer
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(0, |c| c.occupied_len())
+    }
+
+    /// Capacidad total del búfer circular en muestras (0 si no hay sesión).
+    fn buffer_capacity(&self) -> usize {
+        self.consumer
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(0, |c| c.capacity().get())
+    }
+
+    /// Muestras descartadas en la sesión actual por desbordamiento del búfer.
+    fn dropped_samples(&self) -> usize {
+        self.dropped_samples.load(Ordering::Relaxed)
+    }
+
     /// Espera de forma asíncrona a que haya nuevos datos.
     fn wait_for_data<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
         let notify = self.notify.clone();
@@ -210,6 +468,26 @@ impl AudioRecorder {
         })
     }
 
+    /// Descarta las muestras pendientes en el búfer sin detener la grabación.
+    ///
+    /// Solo tiene efecto durante una grabación: `stop()` ya libera el búfer, así
+    /// que fuera de ella no hay nada que descartar y devuelve 0.
+    ///
+    /// Returns:
+    ///     Número de muestras descartadas
+    fn reset(&mut self) -> usize {
+        let discarded = self
+            .consumer
+            .lock()
+            .unwrap()
+            .as_mut()
+            .map_or(0, |c| c.clear());
+        self.dropped_samples.store(0, Ordering::Relaxed);
+
+        info!("AudioRecorder reiniciado ({} muestras descartadas)", discarded);
+        discarded
+    }
+
     fn stop<'py>(&mut self, py: Python<'py>) -> PyResult<&'py PyArray1<f32>> {
         if !self.is_recording {
             return Err(pyo3::exceptions::PyRuntimeError::new_err("No se está grabando"));
@@ -218,14 +496,15 @@ impl AudioRecorder {
         self.stream = None;
         self.is_recording = false;
 
-        let mut raw_data = Vec::new();
         let mut guard = self.consumer.lock().unwrap();
-        if let Some(mut consumer) = guard.take() {
-            // ringbuf 0.4: usar pop_iter() o try_pop()
-            while let Some(sample) = consumer.try_pop() {
-                raw_data.push(sample);
-            }
-        }
+        let raw_data = match guard.take() {
+            Some(mut consumer) => pop_samples(&mut consumer, usize::MAX),
+            None => Vec::new(),
+        };
+
+        // Mezclar a mono siempre, con o sin re-muestreo: el re-muestreador trabaja
+        // sobre un solo canal y el llamador espera audio mono
+        let raw_data = downmix_to_mono(raw_data, self.channels);
 
         // Re-muestrear si es necesario
         let final_data = if self.device_sample_rate != self.requested_sample_rate
@@ -236,32 +515,12 @@ impl AudioRecorder {
                 self.device_sample_rate, self.requested_sample_rate
             );
 
-            let params = SincInterpolationParameters {
-                sinc_len: 256,
-                f_cutoff: 0.95,
-                interpolation: SincInterpolationType::Linear,
-                oversampling_factor: 256,
-                window: WindowFunction::BlackmanHarris2,
-            };
-
-            let f_ratio = self.requested_sample_rate as f64 / self.device_sample_rate as f64;
-            let mut resampler = SincFixedIn::<f32>::new(
-                f_ratio,
-                256.0,
-                params,
-                raw_data.len(),
-                1, // canales
-            )
-            .map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("Fallo init re-muestreador: {}", e))
-            })?;
-
-            let waves = vec![raw_data];
-            let resampled_waves = resampler.process(&waves, None).map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("Fallo al re-muestrear: {}", e))
-            })?;
-
-            resampled_waves[0].clone()
+            resample_block(
+                raw_data,
+                self.device_sample_rate,
+                self.requested_sample_rate,
+                self.resample_quality,
+            )?
         } else {
             raw_data
         };
@@ -271,17 +530,208 @@ impl AudioRecorder {
     }
 }
 
+// ============================================================================
+// CAPACIDADES DE DISPOSITIVO - Consulta de configuraciones soportadas
+// ============================================================================
+
+/// Capacidades de captura de un dispositivo de entrada.
+///
+/// Permite a la UI deshabilitar opciones incompatibles y explicar por qué pedir
+/// 16kHz mono en un dispositivo que solo soporta 44.1kHz estéreo implica re-muestreo.
+#[pyclass]
+struct DeviceCapabilities {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    min_sample_rate: u32,
+    #[pyo3(get)]
+    max_sample_rate: u32,
+    #[pyo3(get)]
+    channels: Vec<u16>,
+    #[pyo3(get)]
+    sample_formats: Vec<String>,
+    /// Rangos (canales, tasa_min, tasa_max) tal como los reporta el host.
+    #[pyo3(get)]
+    configs: Vec<(u16, u32, u32)>,
+}
+
+#[pymethods]
+impl DeviceCapabilities {
+    /// Indica si el dispositivo captura nativamente a `sample_rate` con `channels`
+    /// (sin necesidad de re-muestrear).
+    #[pyo3(signature = (sample_rate=16000, channels=1))]
+    fn supports(&self, sample_rate: u32, channels: u16) -> bool {
+        self.configs
+            .iter()
+            .any(|&(ch, min, max)| ch == channels && min <= sample_rate && sample_rate <= max)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DeviceCapabilities(name={:?}, rates={}-{}Hz, channels={:?}, formats={:?})",
+            self.name, self.min_sample_rate, self.max_sample_rate, self.channels, self.sample_formats
+        )
+    }
+}
+
+/// Consulta las configuraciones de entrada soportadas por un dispositivo.
+///
+/// Args:
+///     device_name: Nombre del dispositivo; si es None se usa el de entrada por defecto.
+///
+/// Returns:
+///     DeviceCapabilities con rangos de tasa, canales y formatos de muestra
+#[pyfunction]
+#[pyo3(signature = (device_name=None))]
+fn get_device_capabilities(device_name: Option<&str>) -> PyResult<DeviceCapabilities> {
+    let host = cpal::default_host();
+    let device = match device_name {
+        Some(wanted) => host
+            .input_devices()
+            .map_err(|e| {
+                pyo3::exceptions::PyOSError::new_err(format!(
+                    "Fallo al enumerar dispositivos: {}",
+                    e
+                ))
+            })?
+            .find(|d| d.name().map(|n| n == wanted).unwrap_or(false)),
+        None => host.default_input_device(),
+    }
+    .ok_or_else(|| {
+        pyo3::exceptions::PyOSError::new_err("No hay dispositivo de entrada disponible")
+    })?;
+
+    let supported_configs = device.supported_input_configs().map_err(|e| {
+        pyo3::exceptions::PyOSError::new_err(format!(
+            "Fallo al consultar configuraciones del dispositivo: {}",
+            e
+        ))
+    })?;
+
+    let mut caps = DeviceCapabilities {
+        name: device.name().unwrap_or_default(),
+        min_sample_rate: u32::MAX,
+        max_sample_rate: 0,
+        channels: Vec::new(),
+        sample_formats: Vec::new(),
+        configs: Vec::new(),
+    };
+
+    for c in supported_configs {
+        caps.min_sample_rate = caps.min_sample_rate.min(c.min_sample_rate().0);
+        caps.max_sample_rate = caps.max_sample_rate.max(c.max_sample_rate().0);
+        caps.configs.push((c.channels(), c.min_sample_rate().0, c.max_sample_rate().0));
+
+        if !caps.channels.contains(&c.channels()) {
+            caps.channels.push(c.channels());
+        }
+        let format = c.sample_format().to_string();
+        if !caps.sample_formats.contains(&format) {
+            caps.sample_formats.push(format);
+        }
+    }
+
+    if caps.configs.is_empty() {
+        caps.min_sample_rate = 0;
+    }
+    caps.channels.sort_unstable();
+
+    Ok(caps)
+}
+
 // ============================================================================
 // SHARED AUDIO BUFFER - Zero-Copy Bridge via /dev/shm (SOTA 2026)
 // ============================================================================
 
+/// Formato de las muestras almacenadas en memoria compartida.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ShmDtype {
+    /// f32 en [-1.0, 1.0] (4 bytes/muestra). Sin pérdida respecto a la captura.
+    Float32,
+    /// PCM 16-bit (2 bytes/muestra). Mitad de memoria y ancho de banda; la
+    /// cuantización (~96 dB de rango dinámico) se arrastra al re-muestreo posterior.
+    Int16,
+}
+
+impl ShmDtype {
+    fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "float32" => Ok(ShmDtype::Float32),
+            "int16" => Ok(ShmDtype::Int16),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "dtype inválido '{}': usar 'float32' o 'int16'",
+                name
+            ))),
+        }
+    }
+
+    /// Nombre compatible con `numpy.dtype`.
+    fn name(self) -> &'static str {
+        match self {
+            ShmDtype::Float32 => "float32",
+            ShmDtype::Int16 => "int16",
+        }
+    }
+
+    fn item_size(self) -> usize {
+        match self {
+            ShmDtype::Float32 => std::mem::size_of::<f32>(),
+            ShmDtype::Int16 => std::mem::size_of::<i16>(),
+        }
+    }
+}
+
 /// Wrapper thread-safe para puntero a memoria compartida.
 ///
 /// Marcamos como Send+Sync porque el acceso a la memoria compartida es atómico
 /// y controlado vía AtomicUsize para write_pos.
+#[derive(Clone, Copy)]
 struct SharedMemPtr {
-    ptr: *mut f32,
+    ptr: *mut u8,
     capacity: usize,
+    dtype: ShmDtype,
+}
+
+impl SharedMemPtr {
+    /// Escribe `samples` a partir de la muestra `pos`, convirtiendo al dtype del buffer.
+    ///
+    /// SAFETY: `pos + samples.len()` no debe superar `capacity`.
+    unsafe fn write(&self, pos: usize, samples: &[f32]) {
+        match self.dtype {
+            ShmDtype::Float32 => unsafe {
+                std::ptr::copy_nonoverlapping(
+                    samples.as_ptr(),
+                    (self.ptr as *mut f32).add(pos),
+                    samples.len(),
+                );
+            },
+            ShmDtype::Int16 => {
+                let dst = unsafe {
+                    std::slice::from_raw_parts_mut((self.ptr as *mut i16).add(pos), samples.len())
+                };
+                for (d, &s) in dst.iter_mut().zip(samples) {
+                    *d = (s.clamp(-1.0, 1.0) * 32767.0) as i16;
+                }
+            }
+        }
+    }
+
+    /// Copia `len` muestras a partir de `start` como f32, sea cual sea el dtype.
+    ///
+    /// SAFETY: el rango debe estar dentro de la región ya escrita.
+    unsafe fn read(&self, start: usize, len: usize) -> Vec<f32> {
+        match self.dtype {
+            ShmDtype::Float32 => unsafe {
+                std::slice::from_raw_parts((self.ptr as *const f32).add(start), len).to_vec()
+            },
+            ShmDtype::Int16 => unsafe {
+                std::slice::from_raw_parts((self.ptr as *const i16).add(start), len)
+                    .iter()
+                    .map(|&s| s as f32 / 32767.0)
+                    .collect()
+            },
+        }
+    }
 }
 
 // SAFETY: El puntero apunta a memoria compartida mapeada que es válida durante
@@ -300,7 +750,8 @@ unsafe impl Sync for SharedMemPtr {}
 /// from multiprocessing import shared_memory
 ///
 /// shm = shared_memory.SharedMemory(name=recorder.get_shm_name())
-/// audio = np.frombuffer(shm.buf[:recorder.get_data_len() * 4], dtype=np.float32)
+/// dtype = np.dtype(recorder.get_dtype())
+/// audio = np.frombuffer(shm.buf[:recorder.get_data_len() * dtype.itemsize], dtype=dtype)
 /// ```
 ///
 /// Arquitectura:
@@ -312,16 +763,22 @@ pub struct SharedAudioBuffer {
     shmem: Option<Shmem>,
     shm_name: String,
     capacity: usize,
+    dtype: ShmDtype,
     write_pos: Arc<AtomicUsize>,
     is_finalized: Arc<AtomicBool>,
+    /// Contador de sesiones: se incrementa en cada `reset()` para invalidar lectores viejos.
+    session: Arc<AtomicUsize>,
 }
 
 /// Datos compartidos entre el callback de audio y el struct principal.
 /// Separado para permitir Send+Sync en closure de cpal.
+#[derive(Clone)]
 struct SharedBufferState {
     mem_ptr: SharedMemPtr,
     write_pos: Arc<AtomicUsize>,
     is_finalized: Arc<AtomicBool>,
+    /// Contador de sesiones: se incrementa en cada `reset()` para invalidar lectores viejos.
+    session: Arc<AtomicUsize>,
 }
 
 // SAFETY: Los campos internos son thread-safe
@@ -342,24 +799,31 @@ impl SharedBufferState {
         }
 
         // SAFETY: El puntero es válido y estamos dentro de los límites
-        unsafe {
-            std::ptr::copy_nonoverlapping(
-                samples.as_ptr(),
-                self.mem_ptr.ptr.add(current_pos),
-                samples_to_write,
-            );
-        }
+        unsafe { self.mem_ptr.write(current_pos, &samples[..samples_to_write]) };
 
         self.write_pos.fetch_add(samples_to_write, Ordering::Release);
         samples_to_write
     }
+
+    /// Copia `len` muestras a partir de `start`, acotadas a la capacidad del buffer.
+    ///
+    /// El llamador garantiza que `start + len` no supera el `write_pos` observado;
+    /// el acotamiento evita leer fuera del mapeo si esa garantía se rompe.
+    fn read_samples(&self, start: usize, len: usize) -> Vec<f32> {
+        let end = start.saturating_add(len).min(self.mem_ptr.capacity);
+        if start >= end {
+            return Vec::new();
+        }
+        // SAFETY: [start, end) está dentro del mapeo y ya fue escrito (Acquire sobre write_pos)
+        unsafe { self.mem_ptr.read(start, end - start) }
+    }
 }
 
 impl SharedAudioBuffer {
-    fn new_internal(capacity_samples: usize) -> PyResult<Self> {
+    fn new_internal(capacity_samples: usize, dtype: ShmDtype) -> PyResult<Self> {
         // Generar nombre único para el segment de memoria compartida
         let shm_name = format!("v2m_audio_{}", std::process::id());
-        let byte_size = capacity_samples * std::mem::size_of::<f32>();
+        let byte_size = capacity_samples * dtype.item_size();
 
         let shmem = ShmemConf::new()
             .size(byte_size)
@@ -373,33 +837,46 @@ impl SharedAudioBuffer {
             })?;
 
         info!(
-            "SharedAudioBuffer creado: name={}, capacity={} samples ({} bytes)",
-            shm_name, capacity_samples, byte_size
+            "SharedAudioBuffer creado: name={}, capacity={} samples {} ({} bytes)",
+            shm_name, capacity_samples, dtype.name(), byte_size
         );
 
         Ok(Self {
             shmem: Some(shmem),
             shm_name,
             capacity: capacity_samples,
+            dtype,
             write_pos: Arc::new(AtomicUsize::new(0)),
             is_finalized: Arc::new(AtomicBool::new(false)),
+            session: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    /// Puntero tipado a la memoria compartida (None si no fue asignada).
+    fn mem_ptr(&self) -> Option<SharedMemPtr> {
+        self.shmem.as_ref().map(|shmem| SharedMemPtr {
+            ptr: shmem.as_ptr(),
+            capacity: self.capacity,
+            dtype: self.dtype,
         })
     }
 
     /// Crea el estado compartido para el callback de audio.
     fn create_shared_state(&self) -> Option<SharedBufferState> {
-        self.shmem.as_ref().map(|shmem| SharedBufferState {
-            mem_ptr: SharedMemPtr {
-                ptr: shmem.as_ptr() as *mut f32,
-                capacity: self.capacity,
-            },
+        self.mem_ptr().map(|mem_ptr| SharedBufferState {
+            mem_ptr,
             write_pos: self.write_pos.clone(),
             is_finalized: self.is_finalized.clone(),
+            session: self.session.clone(),
         })
     }
 
     /// Reinicia el buffer para una nueva grabación.
+    ///
+    /// La sesión se incrementa antes de retroceder `write_pos`, así un lector que
+    /// observe el nuevo `write_pos` también observa la sesión nueva.
     fn reset(&self) {
+        self.session.fetch_add(1, Ordering::AcqRel);
         self.write_pos.store(0, Ordering::Release);
         self.is_finalized.store(false, Ordering::Release);
     }
@@ -408,6 +885,27 @@ impl SharedAudioBuffer {
     fn finalize(&self) {
         self.is_finalized.store(true, Ordering::Release);
     }
+
+    /// Valida que `[start, start + len)` quepa en la capacidad y devuelve el fin.
+    fn checked_end(&self, start: usize, len: usize) -> PyResult<usize> {
+        match start.checked_add(len) {
+            Some(end) if end <= self.capacity => Ok(end),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Rango fuera de límites: start={}, len={}, capacidad={}",
+                start, len, self.capacity
+            ))),
+        }
+    }
+
+    /// Copia las muestras en `[start, end)`, acotadas a lo ya escrito.
+    fn copy_range(&self, start: usize, end: usize) -> Vec<f32> {
+        let end = end.min(self.write_pos.load(Ordering::Acquire));
+        match self.mem_ptr() {
+            // SAFETY: [start, end) está dentro de la región ya escrita
+            Some(mem_ptr) if end > start => unsafe { mem_ptr.read(start, end - start) },
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[pymethods]
@@ -415,31 +913,65 @@ impl SharedAudioBuffer {
     /// Crea un nuevo buffer de audio en memoria compartida.
     ///
     /// Args:
-    ///     capacity_samples: Número máximo de samples float32 a almacenar.
+    ///     capacity_samples: Número máximo de samples a almacenar.
+    ///     dtype: Formato en memoria: "float32" (por defecto) o "int16" (mitad de bytes).
     ///
     /// Returns:
     ///     SharedAudioBuffer con memoria asignada en /dev/shm
     #[new]
-    #[pyo3(signature = (capacity_samples=9600000))]
-    fn new(capacity_samples: usize) -> PyResult<Self> {
+    #[pyo3(signature = (capacity_samples=9600000, dtype="float32"))]
+    fn new(capacity_samples: usize, dtype: &str) -> PyResult<Self> {
         let _ = pyo3_log::try_init();
-        Self::new_internal(capacity_samples)
+        Self::new_internal(capacity_samples, ShmDtype::parse(dtype)?)
+    }
+
+    /// Obtiene el dtype de NumPy con el que leer la memoria compartida.
+    fn get_dtype(&self) -> &'static str {
+        self.dtype.name()
     }
 
     /// Obtiene el nombre del segmento de memoria compartida.
     ///
     /// Python puede usar este nombre con `multiprocessing.shared_memory.SharedMemory(name=...)`.
+    /// Devuelve "" tras `close()`: el segmento ya no existe.
     fn get_shm_name(&self) -> &str {
+        if self.shmem.is_none() {
+            return "";
+        }
         &self.shm_name
     }
 
-    /// Obtiene el número actual de samples válidos en el buffer.
+    /// Obtiene el número actual de samples válidos en el buffer (0 tras `close()`).
     fn get_data_len(&self) -> usize {
         self.write_pos.load(Ordering::Acquire)
     }
 
-    /// Obtiene la capacidad total del buffer en samples.
+    /// Libera el segmento de /dev/shm de forma determinista (unmap + unlink).
+    ///
+    /// Evita depender del GC de Python para liberar la memoria en daemons de larga
+    /// duración. Tras cerrar, las lecturas devuelven arrays vacíos. Idempotente.
+    ///
+    /// Las vistas `np.frombuffer` abiertas por Python sobre el segmento deben
+    /// liberarse antes (ver `SharedMemory.close()`).
+    fn close(&mut self) {
+        if let Some(shmem) = self.shmem.take() {
+            self.finalize();
+            self.write_pos.store(0, Ordering::Release);
+            drop(shmem);
+            info!("SharedAudioBuffer '{}' cerrado", self.shm_name);
+        }
+    }
+
+    /// Indica si el segmento ya fue liberado con `close()`.
+    fn is_closed(&self) -> bool {
+        self.shmem.is_none()
+    }
+
+    /// Obtiene la capacidad total del buffer en samples (0 tras `close()`).
     fn get_capacity(&self) -> usize {
+        if self.shmem.is_none() {
+            return 0;
+        }
         self.capacity
     }
 
@@ -448,27 +980,27 @@ impl SharedAudioBuffer {
         self.is_finalized.load(Ordering::Acquire)
     }
 
-    /// Lee datos del buffer como NumPy array (fallback con copia si es necesario).
+    /// Copia `len` muestras a partir de `start` como array contiguo.
+    ///
+    /// Lanza ValueError si el rango excede la capacidad. Si parte del rango aún no
+    /// fue escrito, devuelve solo las muestras disponibles (puede ser vacío).
+    fn read_range<'py>(
+        &self,
+        py: Python<'py>,
+        start: usize,
+        len: usize,
+    ) -> PyResult<&'py PyArray1<f32>> {
+        let end = self.checked_end(start, len)?;
+        Ok(PyArray1::from_vec(py, self.copy_range(start, end)))
+    }
+
+    /// Lee datos del buffer como NumPy array float32 (fallback con copia).
     ///
     /// Preferir acceso directo vía `get_shm_name()` + `np.frombuffer` para zero-copy.
+    /// Con dtype "int16" las muestras se convierten a float32 en [-1.0, 1.0].
     fn read_as_numpy<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray1<f32>> {
-        let shmem = match &self.shmem {
-            Some(s) => s,
-            None => return Ok(PyArray1::from_vec(py, Vec::new())),
-        };
-
-        let data_len = self.write_pos.load(Ordering::Acquire);
-        if data_len == 0 {
-            return Ok(PyArray1::from_vec(py, Vec::new()));
-        }
-
         // Copia los datos (fallback seguro)
-        let ptr = shmem.as_ptr() as *const f32;
-        let data: Vec<f32> = unsafe {
-            std::slice::from_raw_parts(ptr, data_len).to_vec()
-        };
-
-        Ok(PyArray1::from_vec(py, data))
+        Ok(PyArray1::from_vec(py, self.copy_range(0, usize::MAX)))
     }
 }
 
@@ -495,6 +1027,7 @@ impl Drop for SharedAudioBuffer {
 /// ```python
 /// recorder = ZeroCopyAudioRecorder()
 /// recorder.start()
+/// dtype = np.dtype(recorder.get_dtype())  # float32 o int16
 ///
 /// # Polling loop (o await para async)
 /// while recorder.is_recording():
@@ -502,7 +1035,7 @@ impl Drop for SharedAudioBuffer {
 ///     # Acceso zero-copy
 ///     shm = shared_memory.SharedMemory(name=recorder.get_shm_name())
 ///     chunk_len = recorder.get_available_samples()
-///     audio = np.frombuffer(shm.buf[:chunk_len * 4], dtype=np.float32)
+///     audio = np.frombuffer(shm.buf[:chunk_len * dtype.itemsize], dtype=dtype)
 ///
 /// # Al finalizar
 /// final_audio = recorder.stop()  # o usar zero-copy
@@ -514,21 +1047,55 @@ pub struct ZeroCopyAudioRecorder {
     command_tx: Sender<AudioCommand>,
     command_rx: Receiver<AudioCommand>,
     notify: Arc<Notify>,
+    /// Posición hasta la que `read_chunk()` ya entregó audio.
+    read_pos: Arc<AtomicUsize>,
+    /// Muestras crudas nuevas que necesita `read_chunk()` para devolver audio.
+    min_unread: Arc<AtomicUsize>,
+    /// Re-muestreador persistente del camino de streaming (None si las tasas coinciden).
+    stream_resampler: Option<StreamResampler>,
     requested_sample_rate: u32,
     device_sample_rate: u32,
     channels: u16,
+    resample_quality: ResampleQuality,
     is_recording: bool,
 }
 
+impl ZeroCopyAudioRecorder {
+    /// Recalcula cuántas muestras crudas nuevas hacen falta para que `read_chunk()`
+    /// devuelva audio: un frame sin re-muestreo, o lo que falta para el siguiente
+    /// bloque del re-muestreador.
+    fn update_min_unread(&self) {
+        let frames = self
+            .stream_resampler
+            .as_ref()
+            .map_or(1, StreamResampler::samples_to_next_block);
+        self.min_unread
+            .store(frames * self.channels.max(1) as usize, Ordering::Release);
+    }
+}
+
 #[pymethods]
 impl ZeroCopyAudioRecorder {
+    /// Args:
+    ///     sample_rate: Tasa objetivo en Hz.
+    ///     channels: Número de canales a capturar.
+    ///     max_duration_sec: Duración máxima que cabe en la memoria compartida.
+    ///     resample_quality: Preset del re-muestreador: "fast", "balanced" o "high".
+    ///     dtype: Formato en memoria compartida: "float32" o "int16" (ver `get_dtype()`).
     #[new]
-    #[pyo3(signature = (sample_rate=16000, channels=1, max_duration_sec=600))]
-    fn new(sample_rate: u32, channels: u16, max_duration_sec: u32) -> PyResult<Self> {
+    #[pyo3(signature = (sample_rate=16000, channels=1, max_duration_sec=600, resample_quality="high", dtype="float32"))]
+    fn new(
+        sample_rate: u32,
+        channels: u16,
+        max_duration_sec: u32,
+        resample_quality: &str,
+        dtype: &str,
+    ) -> PyResult<Self> {
         let _ = pyo3_log::try_init();
 
+        let resample_quality = ResampleQuality::parse(resample_quality)?;
         let capacity = (sample_rate * max_duration_sec) as usize;
-        let shared_buffer = SharedAudioBuffer::new_internal(capacity)?;
+        let shared_buffer = SharedAudioBuffer::new_internal(capacity, ShmDtype::parse(dtype)?)?;
         let (command_tx, command_rx) = flume::unbounded();
 
         Ok(ZeroCopyAudioRecorder {
@@ -537,9 +1104,13 @@ impl ZeroCopyAudioRecorder {
             command_tx,
             command_rx,
             notify: Arc::new(Notify::new()),
+            read_pos: Arc::new(AtomicUsize::new(0)),
+            min_unread: Arc::new(AtomicUsize::new(channels.max(1) as usize)),
+            stream_resampler: None,
             requested_sample_rate: sample_rate,
             device_sample_rate: 0,
             channels,
+            resample_quality,
             is_recording: false,
         })
     }
@@ -553,6 +1124,7 @@ impl ZeroCopyAudioRecorder {
 
         // Reset del buffer compartido
         self.shared_buffer.reset();
+        self.read_pos.store(0, Ordering::Release);
 
         let host = cpal::default_host();
         let device = match host.default_input_device() {
@@ -605,6 +1177,17 @@ impl ZeroCopyAudioRecorder {
             self.requested_sample_rate, self.device_sample_rate
         );
 
+        self.stream_resampler = if self.device_sample_rate != self.requested_sample_rate {
+            Some(StreamResampler::new(
+                self.device_sample_rate,
+                self.requested_sample_rate,
+                self.resample_quality,
+            )?)
+        } else {
+            None
+        };
+        self.update_min_unread();
+
         // Crear estado compartido thread-safe para el callback
         let shared_state = self.shared_buffer.create_shared_state()
             .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("SharedMemory no inicializada"))?;
@@ -624,6 +1207,9 @@ impl ZeroCopyAudioRecorder {
                     if written > 0 {
                         // Notificación lock-free vía flume
                         let _ = command_tx.try_send(AudioCommand::DataAvailable(written));
+                        // Despertar a todos los que esperan (wait_for_data y cada iterador
+                        // de stream_chunks); notify_one deja un permiso para el que aún no espera
+                        notify.notify_waiters();
                         notify.notify_one();
                     }
                 },
@@ -653,6 +1239,11 @@ impl ZeroCopyAudioRecorder {
         &self.shared_buffer.shm_name
     }
 
+    /// Obtiene el dtype de NumPy con el que leer la memoria compartida.
+    fn get_dtype(&self) -> &'static str {
+        self.shared_buffer.dtype.name()
+    }
+
     /// Obtiene el número de samples disponibles en el buffer.
     fn get_available_samples(&self) -> usize {
         self.shared_buffer.write_pos.load(Ordering::Acquire)
@@ -663,10 +1254,52 @@ impl ZeroCopyAudioRecorder {
         self.is_recording
     }
 
-    /// Espera de forma asíncrona a que haya nuevos datos (usa polling simple).
+    /// Copia `len` muestras crudas (tasa del dispositivo) a partir de `start`.
+    ///
+    /// Evita aritmética manual sobre el memoryview de `shared_memory`. No altera la
+    /// posición de `read_chunk()`. Lanza ValueError si el rango excede la capacidad;
+    /// si parte del rango aún no fue escrito, devuelve solo lo disponible.
+    fn read_range<'py>(
+        &self,
+        py: Python<'py>,
+        start: usize,
+        len: usize,
+    ) -> PyResult<&'py PyArray1<f32>> {
+        let end = self.shared_buffer.checked_end(start, len)?;
+        Ok(PyArray1::from_vec(py, self.shared_buffer.copy_range(start, end)))
+    }
+
+    /// Devuelve `(write_pos, capacity, is_finalized, shm_name)` en una sola llamada.
+    ///
+    /// `is_finalized` se lee antes que `write_pos`: si ya está finalizado, la
+    /// posición observada es la definitiva y la instantánea es coherente.
+    fn get_buffer_stats(&self) -> (usize, usize, bool, String) {
+        let buffer = &self.shared_buffer;
+        let is_finalized = buffer.is_finalized.load(Ordering::Acquire);
+        let write_pos = buffer.write_pos.load(Ordering::Acquire);
+        (write_pos, buffer.capacity, is_finalized, buffer.shm_name.clone())
+    }
+
+    /// Fracción ocupada del buffer compartido (0.0 - 1.0).
+    ///
+    /// Al llegar a 1.0 el callback deja de aceptar muestras.
+    fn utilization(&self) -> f64 {
+        let capacity = self.shared_buffer.capacity;
+        if capacity == 0 {
+            return 0.0;
+        }
+        self.shared_buffer.write_pos.load(Ordering::Acquire) as f64 / capacity as f64
+    }
+
+    /// Espera de forma asíncrona a que `read_chunk()` tenga audio que devolver.
+    ///
+    /// Con re-muestreo espera a que las muestras sin leer completen un bloque
+    /// del re-muestreador, no solo a que exista alguna muestra nueva.
     fn wait_for_data<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
         let notify = self.notify.clone();
         let write_pos = self.shared_buffer.write_pos.clone();
+        let read_pos = self.read_pos.clone();
+        let min_unread = self.min_unread.clone();
         let is_finalized = self.shared_buffer.is_finalized.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
@@ -675,7 +1308,10 @@ impl ZeroCopyAudioRecorder {
                     return Err(pyo3::exceptions::PyRuntimeError::new_err("Stream closed"));
                 }
 
-                if write_pos.load(Ordering::Acquire) > 0 {
+                let unread = write_pos
+                    .load(Ordering::Acquire)
+                    .saturating_sub(read_pos.load(Ordering::Acquire));
+                if unread >= min_unread.load(Ordering::Acquire) {
                     return Ok(());
                 }
 
@@ -684,11 +1320,113 @@ impl ZeroCopyAudioRecorder {
         })
     }
 
-    /// Lee chunk de datos como NumPy array (copia necesaria para re-muestreo).
-    fn read_chunk<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray1<f32>> {
-        // Para streaming, usamos acceso directo vía shm_name + np.frombuffer en Python
-        // Este método es fallback con copia
-        self.shared_buffer.read_as_numpy(py)
+    /// Lee el audio nuevo desde la última llamada, mezclado a mono y re-muestreado
+    /// a la tasa solicitada.
+    ///
+    /// El re-muestreador es persistente durante la grabación, así que los chunks
+    /// concatenados forman una señal continua (sin transitorios en los bordes).
+    /// Las muestras que no completan un bloque interno se entregan en la siguiente
+    /// llamada: sin un `await wait_for_data()` previo el resultado puede ser vacío.
+    ///
+    /// Tras `stop()`, la siguiente llamada entrega lo que quedaba sin leer y vacía
+    /// el re-muestreador (`flush`): la concatenación de todos los chunks de la
+    /// sesión coincide entonces con el resultado de `stop()`.
+    fn read_chunk<'py>(&mut self, py: Python<'py>) -> PyResult<&'py PyArray1<f32>> {
+        let frame_len = self.channels.max(1) as usize;
+        let start = self.read_pos.load(Ordering::Acquire);
+        let mut raw = self.shared_buffer.copy_range(start, usize::MAX);
+        // Solo frames completos: el resto se lee en la siguiente llamada
+        raw.truncate(raw.len() / frame_len * frame_len);
+        let read = raw.len();
+        let mono = downmix_to_mono(raw, self.channels);
+
+        let chunk = if self.is_recording {
+            match self.stream_resampler.as_mut() {
+                Some(resampler) => resampler.process(&mono)?,
+                None => mono,
+            }
+        } else {
+            // Grabación detenida: no llegarán más muestras, drenar la línea de retardo
+            match self.stream_resampler.take() {
+                Some(mut resampler) => {
+                    let mut out = resampler.process(&mono)?;
+                    out.extend(resampler.flush()?);
+                    out
+                }
+                None => mono,
+            }
+        };
+
+        self.update_min_unread();
+        self.read_pos.store(start + read, Ordering::Release);
+        Ok(PyArray1::from_vec(py, chunk))
+    }
+
+    /// Inicia la grabación (si no está en curso) y devuelve un iterador asíncrono
+    /// de chunks mono de duración fija, re-muestreados a la tasa solicitada.
+    ///
+    /// Cada chunk tiene exactamente `sample_rate * chunk_ms / 1000` muestras; el
+    /// audio multicanal se mezcla a mono antes de re-muestrear. El iterador termina
+    /// tras `stop()`, después de entregar el audio pendiente (solo el último chunk
+    /// puede ser más corto, nunca vacío). Si se llama a `start()` de nuevo antes
+    /// de agotarlo, termina sin entregar audio de la sesión nueva.
+    ///
+    /// ```python
+    /// async for chunk in recorder.stream_chunks(100):
+    ///     transcriber.feed(chunk)
+    /// ```
+    ///
+    /// Args:
+    ///     chunk_ms: Duración de cada chunk en milisegundos.
+    ///     on_overrun: Qué hacer si el productor adelanta al consumidor:
+    ///         - "block": entregar todos los chunks en orden (la latencia crece).
+    ///         - "drop": descartar los chunks atrasados y entregar el más reciente.
+    #[pyo3(signature = (chunk_ms=100, on_overrun="block"))]
+    fn stream_chunks(
+        mut slf: PyRefMut<'_, Self>,
+        chunk_ms: u32,
+        on_overrun: &str,
+    ) -> PyResult<AudioChunkStream> {
+        if chunk_ms == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("chunk_ms debe ser mayor a 0"));
+        }
+        let overrun = ChunkOverrun::parse(on_overrun)?;
+
+        if !slf.is_recording {
+            slf.start()?;
+        }
+
+        let source = slf.shared_buffer.create_shared_state()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("SharedMemory no inicializada"))?;
+        let resampler = if slf.device_sample_rate != slf.requested_sample_rate {
+            Some(StreamResampler::new(
+                slf.device_sample_rate,
+                slf.requested_sample_rate,
+                slf.resample_quality,
+            )?)
+        } else {
+            None
+        };
+        // Los chunks se cortan a la tasa solicitada, después de re-muestrear
+        let chunk_samples = (slf.requested_sample_rate as usize * chunk_ms as usize / 1000).max(1);
+
+        let session = source.session.load(Ordering::Acquire);
+
+        Ok(AudioChunkStream {
+            source,
+            session,
+            notify: slf.notify.clone(),
+            state: Arc::new(Mutex::new(ChunkStreamState {
+                read_pos: 0,
+                resampler,
+                ready: Vec::new(),
+            })),
+            exhausted: Arc::new(AtomicBool::new(false)),
+            chunk_samples,
+            channels: slf.channels.max(1),
+            overrun,
+            owner: slf.into(),
+        })
     }
 
     /// Detiene la grabación y devuelve el audio re-muestreado.
@@ -701,58 +1439,36 @@ impl ZeroCopyAudioRecorder {
         self.is_recording = false;
         self.shared_buffer.finalize();
 
-        // Notificar cierre vía canal
+        // Notificar cierre vía canal y despertar a todos los que esperan datos
+        // (wait_for_data y los iteradores de stream_chunks comparten el Notify).
+        // notify_one además deja un permiso para quien aún no llegó a esperar.
         let _ = self.command_tx.try_send(AudioCommand::Stopped);
+        self.notify.notify_waiters();
+        self.notify.notify_one();
 
-        // Leer datos del buffer compartido
-        let shmem = match &self.shared_buffer.shmem {
-            Some(s) => s,
-            None => return Ok(PyArray1::from_vec(py, Vec::new())),
-        };
-
-        let data_len = self.shared_buffer.write_pos.load(Ordering::Acquire);
-        if data_len == 0 {
+        // Leer datos del buffer compartido. `stream_resampler` se conserva para que
+        // un read_chunk() posterior entregue y vacíe lo que quedó pendiente
+        let raw_data = downmix_to_mono(self.shared_buffer.copy_range(0, usize::MAX), self.channels);
+        if raw_data.is_empty() {
             return Ok(PyArray1::from_vec(py, Vec::new()));
         }
 
-        let ptr = shmem.as_ptr() as *const f32;
-        let raw_data: Vec<f32> = unsafe {
-            std::slice::from_raw_parts(ptr, data_len).to_vec()
-        };
-
-        // Re-muestrear si es necesario
-        let final_data = if self.device_sample_rate != self.requested_sample_rate && !raw_data.is_empty() {
+        // Re-muestrear por bloques y vaciar con flush(): el resultado es idéntico a
+        // la concatenación de los read_chunk() de la sesión, incluido el posterior a stop()
+        let final_data = if self.device_sample_rate != self.requested_sample_rate {
             info!(
                 "Re-muestrando de {}Hz a {}Hz ({} samples)",
                 self.device_sample_rate, self.requested_sample_rate, raw_data.len()
             );
 
-            let params = SincInterpolationParameters {
-                sinc_len: 256,
-                f_cutoff: 0.95,
-                interpolation: SincInterpolationType::Linear,
-                oversampling_factor: 256,
-                window: WindowFunction::BlackmanHarris2,
-            };
-
-            let f_ratio = self.requested_sample_rate as f64 / self.device_sample_rate as f64;
-            let mut resampler = SincFixedIn::<f32>::new(
-                f_ratio,
-                256.0,
-                params,
-                raw_data.len(),
-                1,
-            )
-            .map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("Fallo init re-muestreador: {}", e))
-            })?;
-
-            let waves = vec![raw_data];
-            let resampled_waves = resampler.process(&waves, None).map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("Fallo al re-muestrear: {}", e))
-            })?;
-
-            resampled_waves[0].clone()
+            let mut resampler = StreamResampler::new(
+                self.device_sample_rate,
+                self.requested_sample_rate,
+                self.resample_quality,
+            )?;
+            let mut resampled = resampler.process(&raw_data)?;
+            resampled.extend(resampler.flush()?);
+            resampled
         } else {
             raw_data
         };
@@ -762,6 +1478,178 @@ impl ZeroCopyAudioRecorder {
     }
 }
 
+/// Política cuando el productor genera audio más rápido de lo que se consume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkOverrun {
+    /// Entregar todos los chunks pendientes en orden.
+    Block,
+    /// Saltar los chunks completos atrasados y entregar solo el más reciente.
+    Drop,
+}
+
+impl ChunkOverrun {
+    fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "block" => Ok(ChunkOverrun::Block),
+            "drop" => Ok(ChunkOverrun::Drop),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(
+                "on_overrun debe ser 'block' o 'drop'",
+            )),
+        }
+    }
+}
+
+/// Estado de lectura de un `AudioChunkStream`, compartido entre sus futuros.
+struct ChunkStreamState {
+    /// Posición (en muestras intercaladas del dispositivo) hasta la que ya se leyó.
+    read_pos: usize,
+    /// Re-muestreador propio del iterador (None si las tasas coinciden).
+    resampler: Option<StreamResampler>,
+    /// Audio mono a la tasa solicitada aún no entregado.
+    ready: Vec<f32>,
+}
+
+impl ChunkStreamState {
+    /// Lee los frames completos nuevos hasta `available`, los mezcla a mono y los
+    /// re-muestrea hacia `ready`.
+    fn pull(&mut self, source: &SharedBufferState, available: usize, channels: u16) -> PyResult<()> {
+        let frame_len = channels as usize;
+        let len = (available - self.read_pos) / frame_len * frame_len;
+        if len == 0 {
+            return Ok(());
+        }
+
+        let raw = source.read_samples(self.read_pos, len);
+        self.read_pos += len;
+
+        let mono = downmix_to_mono(raw, channels);
+        match self.resampler.as_mut() {
+            Some(r) => self.ready.extend(r.process(&mono)?),
+            None => self.ready.extend(mono),
+        }
+        Ok(())
+    }
+
+    /// Extrae el siguiente chunk de `chunk_samples` muestras, si está completo.
+    ///
+    /// Con `finalized` vacía primero el re-muestreador y entrega el remanente como
+    /// último chunk (más corto). Nunca devuelve un chunk vacío.
+    fn take_chunk(
+        &mut self,
+        chunk_samples: usize,
+        overrun: ChunkOverrun,
+        finalized: bool,
+    ) -> PyResult<Option<Vec<f32>>> {
+        if finalized {
+            // No llegarán más muestras: drenar la línea de retardo
+            if let Some(mut resampler) = self.resampler.take() {
+                self.ready.extend(resampler.flush()?);
+            }
+        }
+
+        let pending = self.ready.len();
+        if pending >= chunk_samples {
+            if overrun == ChunkOverrun::Drop && pending >= 2 * chunk_samples {
+                let skipped = (pending / chunk_samples - 1) * chunk_samples;
+                warn!("stream_chunks: consumidor atrasado, descartando {} muestras", skipped);
+                self.ready.drain(..skipped);
+            }
+            Ok(Some(self.ready.drain(..chunk_samples).collect()))
+        } else if finalized && pending > 0 {
+            Ok(Some(std::mem::take(&mut self.ready)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Resultado de una pasada de `AudioChunkStream.__anext__`.
+enum ChunkStep {
+    Chunk(Vec<f32>),
+    Wait,
+    End,
+}
+
+/// Iterador asíncrono de chunks devuelto por `ZeroCopyAudioRecorder.stream_chunks()`.
+///
+/// Lee de la memoria compartida con un cursor propio, sin interferir con
+/// `get_available_samples()` ni con el resultado de `stop()`.
+#[pyclass(unsendable)]
+struct AudioChunkStream {
+    source: SharedBufferState,
+    /// Sesión de grabación a la que pertenece el iterador.
+    session: usize,
+    notify: Arc<Notify>,
+    state: Arc<Mutex<ChunkStreamState>>,
+    exhausted: Arc<AtomicBool>,
+    /// Muestras por chunk a la tasa solicitada.
+    chunk_samples: usize,
+    channels: u16,
+    overrun: ChunkOverrun,
+    /// Mantiene vivo el grabador (y su memoria compartida) mientras se itera.
+    owner: Py<ZeroCopyAudioRecorder>,
+}
+
+#[pymethods]
+impl AudioChunkStream {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        if self.exhausted.load(Ordering::Acquire) {
+            return Ok(None);
+        }
+
+        let source = self.source.clone();
+        let session = self.session;
+        let notify = self.notify.clone();
+        let state = self.state.clone();
+        let exhausted = self.exhausted.clone();
+        let chunk_samples = self.chunk_samples;
+        let channels = self.channels;
+        let overrun = self.overrun;
+        let owner = self.owner.clone_ref(py);
+
+        let fut = pyo3_asyncio::tokio::future_into_py(py, async move {
+            let _owner = owner;
+            loop {
+                let step = {
+                    let mut st = state.lock().unwrap();
+                    let finalized = source.is_finalized.load(Ordering::Acquire);
+                    let available = source.write_pos.load(Ordering::Acquire);
+
+                    // Un start() posterior reinicia el buffer: este iterador ya no tiene
+                    // datos válidos que leer y termina
+                    if source.session.load(Ordering::Acquire) != session || available < st.read_pos {
+                        ChunkStep::End
+                    } else {
+                        st.pull(&source, available, channels)?;
+                        match st.take_chunk(chunk_samples, overrun, finalized)? {
+                            Some(chunk) => ChunkStep::Chunk(chunk),
+                            None if finalized => ChunkStep::End,
+                            None => ChunkStep::Wait,
+                        }
+                    }
+                };
+
+                match step {
+                    ChunkStep::Chunk(chunk) => {
+                        return Ok(Python::with_gil(|py| PyArray1::from_vec(py, chunk).to_object(py)));
+                    }
+                    ChunkStep::End => {
+                        exhausted.store(true, Ordering::Release);
+                        return Err(pyo3::exceptions::PyStopAsyncIteration::new_err(()));
+                    }
+                    ChunkStep::Wait => notify.notified().await,
+                }
+            }
+        })?;
+
+        Ok(Some(fut.into()))
+    }
+}
+
 // ============================================================================
 // DETECTOR DE ACTIVIDAD DE VOZ (VAD) - WebRTC VAD (SOTA 2026)
 // ============================================================================
@@ -779,7 +1667,150 @@ impl ZeroCopyAudioRecorder {
 #[pyclass(unsendable)]
 struct VoiceActivityDetector {
     vad: webrtc_vad::Vad,
-    sample_rate: webrtc_vad::SampleRate,
+    aggressiveness: i32,
+    sample_rate: u32,
+}
+
+/// Regiones de voz como tuplas (muestra_inicio, muestra_fin).
+type Segments = Vec<(usize, usize)>;
+
+/// Construye un VAD WebRTC validando tasa y agresividad.
+fn build_vad(aggressiveness: i32, sample_rate: u32) -> PyResult<webrtc_vad::Vad> {
+    let sr = match sample_rate {
+        8000 => webrtc_vad::SampleRate::Rate8kHz,
+        16000 => webrtc_vad::SampleRate::Rate16kHz,
+        32000 => webrtc_vad::SampleRate::Rate32kHz,
+        48000 => webrtc_vad::SampleRate::Rate48kHz,
+        _ => {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Tasa de muestreo debe ser 8000, 16000, 32000, o 48000",
+            ))
+        }
+    };
+
+    let mode = match aggressiveness {
+        0 => webrtc_vad::VadMode::Quality,
+        1 => webrtc_vad::VadMode::LowBitrate,
+        2 => webrtc_vad::VadMode::Aggressive,
+        3 => webrtc_vad::VadMode::VeryAggressive,
+        _ => {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Agresividad debe ser 0-3",
+            ))
+        }
+    };
+
+    Ok(webrtc_vad::Vad::new_with_rate_and_mode(sr, mode))
+}
+
+/// Segmenta audio PCM 16-bit en regiones de voz continua con el VAD dado.
+fn segment_frames(
+    vad: &mut webrtc_vad::Vad,
+    audio_i16: &[i16],
+    frame_samples: usize,
+    min_speech_frames: usize,
+    min_silence_frames: usize,
+) -> Vec<(usize, usize)> {
+    let total_frames = audio_i16.len() / frame_samples;
+    let mut segments: Vec<(usize, usize)> = Vec::new();
+
+    let mut in_speech = false;
+    let mut speech_start = 0;
+    let mut speech_frame_count = 0;
+    let mut silence_frame_count = 0;
+
+    for frame_idx in 0..total_frames {
+        let start = frame_idx * frame_samples;
+        let end = start + frame_samples;
+        let frame = &audio_i16[start..end];
+
+        let is_voice = vad.is_voice_segment(frame).unwrap_or(false);
+
+        if is_voice {
+            silence_frame_count = 0;
+            speech_frame_count += 1;
+
+            if !in_speech && speech_frame_count >= min_speech_frames {
+                // Inicio de segmento de voz
+                in_speech = true;
+                speech_start = (frame_idx + 1 - min_speech_frames) * frame_samples;
+            }
+        } else {
+            if in_speech {
+                silence_frame_count += 1;
+
+                if silence_frame_count >= min_silence_frames {
+                    // Fin de segmento de voz
+                    let speech_end = (frame_idx - min_silence_frames) * frame_samples;
+                    if speech_end > speech_start {
+                        segments.push((speech_start, speech_end));
+                    }
+                    in_speech = false;
+                    speech_frame_count = 0;
+                }
+            } else {
+                speech_frame_count = 0;
+            }
+        }
+    }
+
+    // Manejar caso donde el audio termina durante voz
+    if in_speech {
+        segments.push((speech_start, audio_i16.len()));
+    }
+
+    segments
+}
+
+/// Muestras por frame para `frame_ms` a `sample_rate`, validando la duración.
+fn vad_frame_samples(sample_rate: u32, frame_ms: u32) -> PyResult<usize> {
+    // Validar tamaño de frame
+    if frame_ms != 10 && frame_ms != 20 && frame_ms != 30 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "frame_ms debe ser 10, 20, o 30",
+        ));
+    }
+    Ok((sample_rate * frame_ms / 1000) as usize)
+}
+
+impl VoiceActivityDetector {
+    /// Muestras por frame para `frame_ms` a la tasa configurada.
+    fn frame_samples(&self, frame_ms: u32) -> PyResult<usize> {
+        vad_frame_samples(self.sample_rate, frame_ms)
+    }
+
+    /// Tamaños de frame (10/20/30ms) válidos a la tasa configurada.
+    fn frame_sizes(&self) -> Vec<usize> {
+        [10, 20, 30]
+            .iter()
+            .map(|&ms| (self.sample_rate * ms / 1000) as usize)
+            .collect()
+    }
+
+    /// Verifica que `len` sea un tamaño de frame aceptado por WebRTC VAD.
+    fn check_frame_len(&self, len: usize) -> PyResult<()> {
+        let sizes = self.frame_sizes();
+        if sizes.contains(&len) {
+            return Ok(());
+        }
+        let expected = sizes
+            .iter()
+            .map(usize::to_string)
+            .collect::<Vec<_>>()
+            .join("/");
+        Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Frame de {} muestras inválido, se esperaba {} a {}Hz",
+            len, expected, self.sample_rate
+        )))
+    }
+}
+
+/// Convierte muestras f32 en [-1.0, 1.0] a PCM 16-bit.
+fn to_pcm16(audio: &[f32]) -> Vec<i16> {
+    audio
+        .iter()
+        .map(|&s| (s.clamp(-1.0, 1.0) * 32767.0) as i16)
+        .collect()
 }
 
 #[pymethods]
@@ -789,34 +1820,11 @@ impl VoiceActivityDetector {
     fn new(aggressiveness: i32, sample_rate: u32) -> PyResult<Self> {
         let _ = pyo3_log::try_init();
 
-        let sr = match sample_rate {
-            8000 => webrtc_vad::SampleRate::Rate8kHz,
-            16000 => webrtc_vad::SampleRate::Rate16kHz,
-            32000 => webrtc_vad::SampleRate::Rate32kHz,
-            48000 => webrtc_vad::SampleRate::Rate48kHz,
-            _ => {
-                return Err(pyo3::exceptions::PyValueError::new_err(
-                    "Tasa de muestreo debe ser 8000, 16000, 32000, o 48000",
-                ))
-            }
-        };
-
-        let mut vad = webrtc_vad::Vad::new();
-        vad.set_mode(match aggressiveness {
-            0 => webrtc_vad::VadMode::Quality,
-            1 => webrtc_vad::VadMode::LowBitrate,
-            2 => webrtc_vad::VadMode::Aggressive,
-            3 => webrtc_vad::VadMode::VeryAggressive,
-            _ => {
-                return Err(pyo3::exceptions::PyValueError::new_err(
-                    "Agresividad debe ser 0-3",
-                ))
-            }
-        });
+        let vad = build_vad(aggressiveness, sample_rate)?;
 
         info!("VAD inicializado: agresividad={}, tasa={}Hz", aggressiveness, sample_rate);
 
-        Ok(VoiceActivityDetector { vad, sample_rate: sr })
+        Ok(VoiceActivityDetector { vad, aggressiveness, sample_rate })
     }
 
     /// Verifica si un solo frame contiene voz.
@@ -831,16 +1839,68 @@ impl VoiceActivityDetector {
     ///     True si se detecta voz, False en caso contrario
     fn is_speech(&mut self, frame: &PyArray1<i16>) -> PyResult<bool> {
         let slice = unsafe { frame.as_slice()? };
+        self.check_frame_len(slice.len())?;
 
         match self.vad.is_voice_segment(slice) {
             Ok(result) => Ok(result),
             Err(e) => Err(pyo3::exceptions::PyValueError::new_err(format!(
-                "Error VAD: {:?}. Frame debe ser 10/20/30ms (160/320/480 muestras a 16kHz)",
+                "Error VAD: {:?}",
                 e
             ))),
         }
     }
 
+    /// Clasifica un frame y mide su nivel en una sola llamada.
+    ///
+    /// Pensado para bucles de UI que muestran indicador de voz y medidor de nivel
+    /// a la vez, sin recorrer el array otra vez en Python.
+    ///
+    /// Args:
+    ///     frame: Frame Float32 en [-1.0, 1.0] de 10/20/30ms a la tasa configurada
+    ///
+    /// Returns:
+    ///     Tupla (is_speech, rms, peak)
+    fn analyze_frame(&mut self, frame: &PyArray1<f32>) -> PyResult<(bool, f32, f32)> {
+        let slice = unsafe { frame.as_slice()? };
+        self.check_frame_len(slice.len())?;
+
+        let mut sum_sq = 0.0f32;
+        let mut peak = 0.0f32;
+        let pcm: Vec<i16> = slice
+            .iter()
+            .map(|&s| {
+                sum_sq += s * s;
+                peak = peak.max(s.abs());
+                (s.clamp(-1.0, 1.0) * 32767.0) as i16
+            })
+            .collect();
+        let rms = (sum_sq / slice.len() as f32).sqrt();
+
+        let is_speech = self.vad.is_voice_segment(&pcm).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Error VAD: {:?}", e))
+        })?;
+
+        Ok((is_speech, rms, peak))
+    }
+
+    /// Tamaños de frame válidos (10, 20 y 30ms) a la tasa configurada.
+    ///
+    /// Para 16kHz: [160, 320, 480].
+    fn valid_frame_sizes(&self) -> Vec<usize> {
+        self.frame_sizes()
+    }
+
+    /// Muestras por frame de `frame_ms` (10, 20, o 30) a la tasa configurada.
+    #[pyo3(signature = (frame_ms=30))]
+    fn frame_size_for(&self, frame_ms: u32) -> PyResult<usize> {
+        self.frame_samples(frame_ms)
+    }
+
+    /// Devuelve la configuración actual como (agresividad, tasa_de_muestreo).
+    fn get_config(&self) -> (i32, u32) {
+        (self.aggressiveness, self.sample_rate)
+    }
+
     /// Procesa el búfer de audio completo y detecta segmentos de voz.
     ///
     /// Escanea el audio en frames de 30ms y devuelve tuplas (inicio, fin)
@@ -851,108 +1911,124 @@ impl VoiceActivityDetector {
     ///     frame_ms: Duración del frame en milisegundos (10, 20, o 30)
     ///     min_speech_frames: Mínimo de frames de voz consecutivos para contar como segmento
     ///     min_silence_frames: Mínimo de frames de silencio consecutivos para terminar segmento
+    ///     pre_roll_ms: Margen agregado antes de cada segmento (evita recortar el primer fonema)
+    ///     post_roll_ms: Margen agregado después de cada segmento
+    ///
+    /// Los márgenes se acotan a los límites del audio y los segmentos que quedan
+    /// solapados tras expandirse se fusionan.
     ///
     /// Returns:
     ///     Lista de tuplas (muestra_inicio, muestra_fin) para regiones de voz
-    #[pyo3(signature = (audio, frame_ms=30, min_speech_frames=3, min_silence_frames=10))]
+    #[pyo3(signature = (audio, frame_ms=30, min_speech_frames=3, min_silence_frames=10, pre_roll_ms=0, post_roll_ms=0))]
     fn detect_segments(
         &mut self,
         audio: &PyArray1<f32>,
         frame_ms: u32,
         min_speech_frames: usize,
         min_silence_frames: usize,
+        pre_roll_ms: u32,
+        post_roll_ms: u32,
     ) -> PyResult<Vec<(usize, usize)>> {
-        let samples_per_sec = match self.sample_rate {
-            webrtc_vad::SampleRate::Rate8kHz => 8000,
-            webrtc_vad::SampleRate::Rate16kHz => 16000,
-            webrtc_vad::SampleRate::Rate32kHz => 32000,
-            webrtc_vad::SampleRate::Rate48kHz => 48000,
-        };
+        let frame_samples = self.frame_samples(frame_ms)?;
+        let audio_i16 = to_pcm16(unsafe { audio.as_slice()? });
+
+        let segments = segment_frames(
+            &mut self.vad,
+            &audio_i16,
+            frame_samples,
+            min_speech_frames,
+            min_silence_frames,
+        );
 
-        let frame_samples = (samples_per_sec * frame_ms / 1000) as usize;
+        let segments = pad_segments(
+            segments,
+            (self.sample_rate as u64 * pre_roll_ms as u64 / 1000) as usize,
+            (self.sample_rate as u64 * post_roll_ms as u64 / 1000) as usize,
+            audio_i16.len(),
+        );
 
-        // Validar tamaño de frame
-        if frame_ms != 10 && frame_ms != 20 && frame_ms != 30 {
-            return Err(pyo3::exceptions::PyValueError::new_err(
-                "frame_ms debe ser 10, 20, o 30",
-            ));
-        }
+        info!("VAD detectó {} segmentos de voz", segments.len());
+        Ok(segments)
+    }
 
-        let audio_slice = unsafe { audio.as_slice()? };
+    /// Detecta segmentos con varios niveles de agresividad sobre el mismo audio.
+    ///
+    /// Pensado para calibración: permite comparar, por ejemplo, agresividad 2 vs 3
+    /// sobre el audio real del usuario sin volver a grabar. Cada nivel usa un VAD
+    /// propio a la tasa configurada; el estado de este detector no se modifica.
+    ///
+    /// Args:
+    ///     audio: Muestras de audio Float32 normalizadas a [-1.0, 1.0]
+    ///     frame_ms: Duración del frame en milisegundos (10, 20, o 30)
+    ///     aggressiveness_levels: Niveles a comparar (0-3)
+    ///     min_speech_frames: Mínimo de frames de voz consecutivos para contar como segmento
+    ///     min_silence_frames: Mínimo de frames de silencio consecutivos para terminar segmento
+    ///
+    /// Returns:
+    ///     Lista de tuplas (agresividad, segmentos) en el orden solicitado
+    #[pyo3(signature = (audio, frame_ms=30, aggressiveness_levels=vec![2, 3], min_speech_frames=3, min_silence_frames=10))]
+    fn detect_segments_multi(
+        &self,
+        audio: &PyArray1<f32>,
+        frame_ms: u32,
+        aggressiveness_levels: Vec<i32>,
+        min_speech_frames: usize,
+        min_silence_frames: usize,
+    ) -> PyResult<Vec<(i32, Segments)>> {
+        let frame_samples = self.frame_samples(frame_ms)?;
 
-        // Convertir f32 a i16
-        let audio_i16: Vec<i16> = audio_slice
+        // Validar todos los niveles antes de procesar
+        let mut vads = aggressiveness_levels
             .iter()
-            .map(|&s| (s.clamp(-1.0, 1.0) * 32767.0) as i16)
+            .map(|&level| build_vad(level, self.sample_rate).map(|vad| (level, vad)))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let audio_i16 = to_pcm16(unsafe { audio.as_slice()? });
+
+        let results = vads
+            .iter_mut()
+            .map(|(level, vad)| {
+                let segments = segment_frames(
+                    vad,
+                    &audio_i16,
+                    frame_samples,
+                    min_speech_frames,
+                    min_silence_frames,
+                );
+                info!("VAD agresividad={}: {} segmentos de voz", level, segments.len());
+                (*level, segments)
+            })
             .collect();
 
-        let total_frames = audio_i16.len() / frame_samples;
-        let mut segments: Vec<(usize, usize)> = Vec::new();
-
-        let mut in_speech = false;
-        let mut speech_start = 0;
-        let mut speech_frame_count = 0;
-        let mut silence_frame_count = 0;
-
-        for frame_idx in 0..total_frames {
-            let start = frame_idx * frame_samples;
-            let end = start + frame_samples;
-            let frame = &audio_i16[start..end];
-
-            let is_voice = self.vad.is_voice_segment(frame).unwrap_or(false);
-
-            if is_voice {
-                silence_frame_count = 0;
-                speech_frame_count += 1;
-
-                if !in_speech && speech_frame_count >= min_speech_frames {
-                    // Inicio de segmento de voz
-                    in_speech = true;
-                    speech_start = (frame_idx - min_speech_frames + 1) * frame_samples;
-                }
-            } else {
-                if in_speech {
-                    silence_frame_count += 1;
-
-                    if silence_frame_count >= min_silence_frames {
-                        // Fin de segmento de voz
-                        let speech_end = (frame_idx - min_silence_frames) * frame_samples;
-                        if speech_end > speech_start {
-                            segments.push((speech_start, speech_end));
-                        }
-                        in_speech = false;
-                        speech_frame_count = 0;
-                    }
-                } else {
-                    speech_frame_count = 0;
-                }
-            }
-        }
-
-        // Manejar caso donde el audio termina durante voz
-        if in_speech {
-            segments.push((speech_start, audio_i16.len()));
-        }
-
-        info!("VAD detectó {} segmentos de voz", segments.len());
-        Ok(segments)
+        Ok(results)
     }
 
     /// Filtrar audio para mantener solo segmentos de voz.
     ///
     /// Retorna un nuevo array conteniendo solo las porciones de voz de la entrada.
-    #[pyo3(signature = (audio, frame_ms=30))]
+    ///
+    /// Args:
+    ///     audio: Muestras de audio Float32 normalizadas a [-1.0, 1.0]
+    ///     frame_ms: Duración del frame en milisegundos (10, 20, o 30)
+    ///     gap_ms: Silencio insertado entre segmentos para que el transcriptor
+    ///         no fusione enunciados distintos (0 = concatenación directa)
+    #[pyo3(signature = (audio, frame_ms=30, gap_ms=0))]
     fn filter_speech<'py>(
         &mut self,
         py: Python<'py>,
         audio: &PyArray1<f32>,
         frame_ms: u32,
+        gap_ms: u32,
         ) -> PyResult<&'py PyArray1<f32>> {
-        let segments = self.detect_segments(audio, frame_ms, 3, 10)?;
+        let segments = self.detect_segments(audio, frame_ms, 3, 10, 0, 0)?;
         let audio_slice = unsafe { audio.as_slice()? };
+        let gap_samples = (self.sample_rate as u64 * gap_ms as u64 / 1000) as usize;
 
         let mut filtered: Vec<f32> = Vec::new();
-        for (start, end) in segments {
+        for (i, (start, end)) in segments.into_iter().enumerate() {
+            if i > 0 {
+                filtered.resize(filtered.len() + gap_samples, 0.0);
+            }
             let end = end.min(audio_slice.len());
             filtered.extend_from_slice(&audio_slice[start..end]);
         }
@@ -969,6 +2045,228 @@ impl VoiceActivityDetector {
     }
 }
 
+/// Expande cada segmento `pre`/`post` muestras, acotado a `[0, len)`, fusionando solapes.
+///
+/// Asume segmentos ordenados por inicio, como los produce `detect_segments`.
+fn pad_segments(
+    segments: Vec<(usize, usize)>,
+    pre: usize,
+    post: usize,
+    len: usize,
+) -> Vec<(usize, usize)> {
+    let mut padded: Vec<(usize, usize)> = Vec::with_capacity(segments.len());
+    for (start, end) in segments {
+        let start = start.saturating_sub(pre);
+        let end = (end + post).min(len);
+
+        match padded.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => padded.push((start, end)),
+        }
+    }
+    padded
+}
+
+// ============================================================================
+// COMPUERTA VAD EN STREAMING - Acumula y emite enunciados completos
+// ============================================================================
+
+/// Resultado de procesar un frame en `StreamingVadGate`.
+enum GateEvent {
+    Continue,
+    SpeechStart,
+    Utterance(Vec<f32>),
+}
+
+/// Compuerta VAD incremental para transcripción en vivo.
+///
+/// Recibe chunks f32 de cualquier tamaño (ya re-muestreados) y acumula el audio
+/// hasta que un enunciado está completo: voz confirmada seguida de silencio
+/// confirmado. Encapsula el buffering que de otro modo cada app reimplementa.
+///
+/// ```python
+/// gate = StreamingVadGate(sample_rate=16000)
+/// async for chunk in recorder.stream_chunks(100):
+///     event, audio = gate.push(chunk)
+///     while event != "continue":
+///         if event == "utterance":
+///             transcriber.transcribe(audio)
+///         event, audio = gate.push(np.empty(0, dtype=np.float32))
+/// ```
+#[pyclass(unsendable)]
+struct StreamingVadGate {
+    vad: webrtc_vad::Vad,
+    frame_samples: usize,
+    min_speech_frames: usize,
+    min_silence_frames: usize,
+    /// Muestras recibidas que aún no se procesaron (frame incompleto o pendientes tras un evento).
+    pending: Vec<f32>,
+    /// Frames de voz candidatos antes de confirmar el inicio.
+    onset: Vec<f32>,
+    /// Enunciado en curso (incluye los frames de `onset` una vez confirmado).
+    utterance: Vec<f32>,
+    in_speech: bool,
+    speech_frames: usize,
+    silence_frames: usize,
+}
+
+impl StreamingVadGate {
+    /// Avanza la máquina de estados con un frame completo.
+    fn step(&mut self, frame: &[f32]) -> GateEvent {
+        let is_voice = self
+            .vad
+            .is_voice_segment(&to_pcm16(frame))
+            .unwrap_or(false);
+
+        if !self.in_speech {
+            if !is_voice {
+                self.onset.clear();
+                self.speech_frames = 0;
+                return GateEvent::Continue;
+            }
+
+            self.onset.extend_from_slice(frame);
+            self.speech_frames += 1;
+            if self.speech_frames < self.min_speech_frames {
+                return GateEvent::Continue;
+            }
+
+            self.in_speech = true;
+            self.silence_frames = 0;
+            self.utterance = std::mem::take(&mut self.onset);
+            return GateEvent::SpeechStart;
+        }
+
+        self.utterance.extend_from_slice(frame);
+        if is_voice {
+            self.silence_frames = 0;
+            return GateEvent::Continue;
+        }
+
+        self.silence_frames += 1;
+        if self.silence_frames < self.min_silence_frames {
+            return GateEvent::Continue;
+        }
+
+        // Fin del enunciado: recortar el silencio de confirmación
+        let speech_len = self
+            .utterance
+            .len()
+            .saturating_sub(self.silence_frames * self.frame_samples);
+        self.utterance.truncate(speech_len);
+        let utterance = std::mem::take(&mut self.utterance);
+        self.in_speech = false;
+        self.speech_frames = 0;
+        self.silence_frames = 0;
+        GateEvent::Utterance(utterance)
+    }
+}
+
+#[pymethods]
+impl StreamingVadGate {
+    /// Args:
+    ///     aggressiveness: Nivel de agresividad del VAD (0-3).
+    ///     sample_rate: Tasa del audio recibido (8000, 16000, 32000 o 48000).
+    ///     frame_ms: Duración del frame VAD en milisegundos (10, 20, o 30).
+    ///     min_speech_frames: Frames de voz consecutivos para confirmar el inicio.
+    ///     min_silence_frames: Frames de silencio consecutivos para cerrar el enunciado.
+    #[new]
+    #[pyo3(signature = (aggressiveness=2, sample_rate=16000, frame_ms=30, min_speech_frames=3, min_silence_frames=10))]
+    fn new(
+        aggressiveness: i32,
+        sample_rate: u32,
+        frame_ms: u32,
+        min_speech_frames: usize,
+        min_silence_frames: usize,
+    ) -> PyResult<Self> {
+        let _ = pyo3_log::try_init();
+
+        let vad = build_vad(aggressiveness, sample_rate)?;
+        let frame_samples = vad_frame_samples(sample_rate, frame_ms)?;
+
+        Ok(StreamingVadGate {
+            vad,
+            frame_samples,
+            min_speech_frames,
+            min_silence_frames,
+            pending: Vec::new(),
+            onset: Vec::new(),
+            utterance: Vec::new(),
+            in_speech: false,
+            speech_frames: 0,
+            silence_frames: 0,
+        })
+    }
+
+    /// Agrega un chunk y procesa frames hasta el primer evento.
+    ///
+    /// Returns:
+    ///     Tupla (evento, audio):
+    ///     - ("continue", None): no hay novedades.
+    ///     - ("speech_start", None): se confirmó el inicio de voz.
+    ///     - ("utterance", array): enunciado completo, listo para transcribir.
+    ///
+    /// Tras un evento distinto de "continue" las muestras restantes quedan pendientes;
+    /// llamar `push` con un array vacío las procesa sin agregar audio.
+    fn push<'py>(
+        &mut self,
+        py: Python<'py>,
+        chunk: &PyArray1<f32>,
+    ) -> PyResult<(&'static str, Option<&'py PyArray1<f32>>)> {
+        self.pending.extend_from_slice(unsafe { chunk.as_slice()? });
+
+        let pending = std::mem::take(&mut self.pending);
+        let mut consumed = 0;
+        let mut event = GateEvent::Continue;
+
+        for frame in pending.chunks_exact(self.frame_samples) {
+            consumed += self.frame_samples;
+            event = self.step(frame);
+            if !matches!(event, GateEvent::Continue) {
+                break;
+            }
+        }
+        self.pending = pending[consumed..].to_vec();
+
+        Ok(match event {
+            GateEvent::Continue => ("continue", None),
+            GateEvent::SpeechStart => ("speech_start", None),
+            GateEvent::Utterance(audio) => ("utterance", Some(PyArray1::from_vec(py, audio))),
+        })
+    }
+
+    /// Cierra el enunciado en curso (p. ej. al detener la grabación).
+    ///
+    /// Devuelve el audio acumulado si había voz confirmada, o None.
+    /// El estado queda listo para un nuevo stream.
+    fn flush<'py>(&mut self, py: Python<'py>) -> Option<&'py PyArray1<f32>> {
+        let utterance = if self.in_speech {
+            let mut audio = std::mem::take(&mut self.utterance);
+            audio.extend_from_slice(&self.pending);
+            Some(PyArray1::from_vec(py, audio))
+        } else {
+            None
+        };
+        self.reset();
+        utterance
+    }
+
+    /// Descarta todo el audio acumulado y vuelve al estado inicial.
+    fn reset(&mut self) {
+        self.pending.clear();
+        self.onset.clear();
+        self.utterance.clear();
+        self.in_speech = false;
+        self.speech_frames = 0;
+        self.silence_frames = 0;
+    }
+
+    /// Indica si hay un enunciado en curso (voz confirmada aún sin cerrar).
+    fn is_in_speech(&self) -> bool {
+        self.in_speech
+    }
+}
+
 // ============================================================================
 // MONITOR DE SISTEMA - Métricas CPU/RAM/GPU
 // ============================================================================
@@ -1053,7 +2351,142 @@ fn v2m_engine(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<AudioRecorder>()?;
     m.add_class::<SharedAudioBuffer>()?;
     m.add_class::<ZeroCopyAudioRecorder>()?;
+    m.add_class::<AudioChunkStream>()?;
     m.add_class::<VoiceActivityDetector>()?;
+    m.add_class::<StreamingVadGate>()?;
     m.add_class::<SystemMonitor>()?;
+    m.add_class::<DeviceCapabilities>()?;
+    m.add_function(wrap_pyfunction!(get_device_capabilities, m)?)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Señal de prueba estéreo intercalada: un tono distinto por canal.
+    fn stereo_tone(frames: usize) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|i| {
+                let t = i as f32 / 48000.0;
+                [
+                    (t * 440.0 * std::f32::consts::TAU).sin() * 0.5,
+                    (t * 660.0 * std::f32::consts::TAU).sin() * 0.5,
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn downmix_to_mono_halves_stereo_at_matching_rates() {
+        // Con tasas iguales stop() no re-muestrea: la salida es solo la mezcla
+        let stereo = stereo_tone(4800);
+        let mono = downmix_to_mono(stereo.clone(), 2);
+
+        assert_eq!(mono.len(), stereo.len() / 2);
+        for (i, sample) in mono.iter().enumerate() {
+            assert_eq!(*sample, (stereo[2 * i] + stereo[2 * i + 1]) / 2.0);
+        }
+
+        assert_eq!(downmix_to_mono(vec![0.5, -0.5, 1.0], 2), vec![0.0]);
+        assert_eq!(downmix_to_mono(vec![0.25, 0.75], 1), vec![0.25, 0.75]);
+    }
+
+    /// Búfer circular de capacidad 16 cuyo contenido da la vuelta al final.
+    fn wrapped_ring(samples: &[f32]) -> RingConsumer {
+        let (mut producer, mut consumer) = HeapRb::<f32>::new(16).split();
+        producer.push_slice(&[9.0; 12]);
+        consumer.skip(12);
+        producer.push_slice(samples);
+        consumer
+    }
+
+    /// Implementación previa a `pop_samples`: un `try_pop` por muestra.
+    fn try_pop_loop(consumer: &mut RingConsumer, max: usize) -> Vec<f32> {
+        let mut data = Vec::new();
+        while data.len() < max {
+            match consumer.try_pop() {
+                Some(sample) => data.push(sample),
+                None => break,
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn pop_samples_matches_try_pop_loop() {
+        let samples: Vec<f32> = (0..10).map(|i| i as f32 * 0.1 - 0.5).collect();
+
+        for max in [0, 1, 4, 10, 11, usize::MAX] {
+            let mut fast = wrapped_ring(&samples);
+            let mut slow = wrapped_ring(&samples);
+            assert_eq!(pop_samples(&mut fast, max), try_pop_loop(&mut slow, max), "max={}", max);
+            assert_eq!(fast.occupied_len(), slow.occupied_len());
+        }
+
+        let mut empty = wrapped_ring(&[]);
+        assert!(pop_samples(&mut empty, usize::MAX).is_empty());
+        assert!(pop_samples(&mut empty, 4).is_empty());
+    }
+
+    #[test]
+    fn stream_resampler_chunked_matches_single_shot() {
+        let input = downmix_to_mono(stereo_tone(48000 + 777), 2);
+
+        let mut single = StreamResampler::new(48000, 16000, ResampleQuality::High).unwrap();
+        let mut expected = single.process(&input).unwrap();
+        expected.extend(single.flush().unwrap());
+
+        // Tamaños irregulares, menores y mayores que un bloque interno
+        let mut chunked = StreamResampler::new(48000, 16000, ResampleQuality::High).unwrap();
+        let mut actual = Vec::new();
+        let mut offset = 0;
+        for (i, size) in [1, 300, 1023, 1025, 4096, 17].iter().cycle().enumerate() {
+            if offset >= input.len() {
+                break;
+            }
+            let end = (offset + size + i % 3).min(input.len());
+            actual.extend(chunked.process(&input[offset..end]).unwrap());
+            offset = end;
+        }
+        actual.extend(chunked.flush().unwrap());
+
+        assert_eq!(actual.len(), (input.len() as f64 / 3.0).round() as usize);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn chunk_stream_yields_fixed_size_mono_chunks() {
+        // 48kHz estéreo -> 16kHz mono, chunks de 20ms (320 muestras): el primer
+        // bloque del re-muestreador llega con menos muestras que un chunk crudo
+        let buffer = SharedAudioBuffer::new_internal(48000 * 2, ShmDtype::Float32).unwrap();
+        let source = buffer.create_shared_state().unwrap();
+        let mut state = ChunkStreamState {
+            read_pos: 0,
+            resampler: Some(StreamResampler::new(48000, 16000, ResampleQuality::Fast).unwrap()),
+            ready: Vec::new(),
+        };
+        let chunk_samples = 16000 * 20 / 1000;
+        let frames = 48000 * 7 / 10 + 123;
+
+        let mut chunks = Vec::new();
+        for callback in stereo_tone(frames).chunks(2 * 441) {
+            source.write_samples(callback);
+            let available = source.write_pos.load(Ordering::Acquire);
+            state.pull(&source, available, 2).unwrap();
+            while let Some(chunk) = state.take_chunk(chunk_samples, ChunkOverrun::Block, false).unwrap() {
+                chunks.push(chunk);
+            }
+        }
+        buffer.finalize();
+        while let Some(chunk) = state.take_chunk(chunk_samples, ChunkOverrun::Block, true).unwrap() {
+            chunks.push(chunk);
+        }
+
+        let (last, full) = chunks.split_last().unwrap();
+        assert!(full.iter().all(|c| c.len() == chunk_samples));
+        assert!(!last.is_empty() && last.len() <= chunk_samples);
+        let total: usize = chunks.iter().map(Vec::len).sum();
+        assert_eq!(total, (frames as f64 / 3.0).round() as usize);
+    }
+}
@@ -0,0 +1,272 @@
+"""Pruebas para el VAD WebRTC del motor Rust (v2m_engine.VoiceActivityDetector).
+
+Propósito
+---------
+Verificar que:
+    * detect_segments expande los segmentos con pre_roll_ms / post_roll_ms.
+    * Los márgenes nunca exceden los límites del audio.
+    * Los segmentos que se solapan tras expandirse se fusionan.
+    * detect_segments_multi devuelve un set de segmentos por nivel de agresividad.
+    * is_speech rechaza frames de tamaño inválido con un mensaje accionable.
+    * filter_speech inserta silencio entre segmentos con gap_ms.
+    * analyze_frame combina clasificación de voz con RMS y pico.
+    * StreamingVadGate emite inicio de voz y enunciados completos por chunks.
+
+Las ráfagas de ruido blanco fuerte son clasificadas como voz por WebRTC VAD
+en modo 0, y el silencio digital nunca lo es, lo que hace las pruebas deterministas.
+
+Requisitos
+----------
+    * Motor Rust compilado (v2m_engine)
+
+Ejecución
+---------
+    >>> pytest tests/unit/test_engine_vad.py -v
+"""
+
+import unittest
+
+import numpy as np
+import pytest
+
+try:
+    from v2m_engine import StreamingVadGate, VoiceActivityDetector
+
+    HAS_RUST_ENGINE = True
+except ImportError:
+    HAS_RUST_ENGINE = False
+    StreamingVadGate = None
+    VoiceActivityDetector = None
+
+
+rust_required = pytest.mark.skipif(not HAS_RUST_ENGINE, reason="Requiere v2m_engine compilado")
+
+SAMPLE_RATE = 16000
+
+
+def _burst_audio(layout: list[tuple[str, float]]) -> np.ndarray:
+    """Construye audio alternando silencio y ráfagas de ruido según `layout`."""
+    rng = np.random.default_rng(1234)
+    parts = []
+    for kind, seconds in layout:
+        n = int(seconds * SAMPLE_RATE)
+        if kind == "voz":
+            parts.append((rng.uniform(-0.5, 0.5, n)).astype(np.float32))
+        else:
+            parts.append(np.zeros(n, dtype=np.float32))
+    return np.concatenate(parts)
+
+
+@rust_required
+class TestDetectSegmentsRoll(unittest.TestCase):
+    """Pruebas para pre_roll_ms / post_roll_ms en detect_segments."""
+
+    def setUp(self) -> None:
+        self.vad = VoiceActivityDetector(aggressiveness=0, sample_rate=SAMPLE_RATE)
+
+    def test_roll_expands_segment(self) -> None:
+        """Verifica que los márgenes amplían el segmento detectado."""
+        # ARRANGE
+        audio = _burst_audio([("silencio", 1.0), ("voz", 1.0), ("silencio", 1.0)])
+
+        # ACT
+        tight = self.vad.detect_segments(audio)
+        padded = self.vad.detect_segments(audio, pre_roll_ms=200, post_roll_ms=100)
+
+        # ASSERT
+        self.assertEqual(len(tight), 1)
+        self.assertEqual(len(padded), 1)
+        self.assertEqual(padded[0][0], tight[0][0] - 3200)
+        self.assertEqual(padded[0][1], tight[0][1] + 1600)
+
+    def test_roll_clamped_to_buffer_bounds(self) -> None:
+        """Verifica que los márgenes no exceden los límites del audio."""
+        # ARRANGE: voz al inicio y al final del buffer
+        audio = _burst_audio([("voz", 0.5), ("silencio", 1.0), ("voz", 0.5)])
+
+        # ACT
+        segments = self.vad.detect_segments(audio, pre_roll_ms=300, post_roll_ms=300)
+
+        # ASSERT
+        self.assertGreaterEqual(len(segments), 1)
+        for start, end in segments:
+            self.assertGreaterEqual(start, 0)
+            self.assertLessEqual(end, len(audio))
+            self.assertLess(start, end)
+
+    def test_overlapping_rolls_merge(self) -> None:
+        """Verifica que segmentos solapados tras expandirse se fusionan en uno."""
+        # ARRANGE: dos ráfagas separadas por 0.5s de silencio
+        audio = _burst_audio([("silencio", 0.5), ("voz", 0.5), ("silencio", 0.5), ("voz", 0.5), ("silencio", 0.5)])
+
+        # ACT
+        tight = self.vad.detect_segments(audio)
+        merged = self.vad.detect_segments(audio, pre_roll_ms=500, post_roll_ms=500)
+
+        # ASSERT
+        self.assertEqual(len(tight), 2)
+        self.assertEqual(len(merged), 1)
+        self.assertEqual(merged[0][0], 0)
+        self.assertLessEqual(merged[0][1], len(audio))
+
+
+@rust_required
+class TestFilterSpeechGap(unittest.TestCase):
+    """Pruebas para gap_ms en filter_speech."""
+
+    def test_gap_inserted_between_segments(self) -> None:
+        """Verifica que se inserta un silencio de gap_ms entre cada par de segmentos."""
+        # ARRANGE
+        vad = VoiceActivityDetector(aggressiveness=0, sample_rate=SAMPLE_RATE)
+        audio = _burst_audio([("silencio", 0.5), ("voz", 0.5), ("silencio", 1.0), ("voz", 0.5), ("silencio", 0.5)])
+        self.assertEqual(len(vad.detect_segments(audio)), 2)
+
+        # ACT
+        joined = vad.filter_speech(audio)
+        gapped = vad.filter_speech(audio, gap_ms=200)
+
+        # ASSERT: un solo hueco de 200ms entre los dos segmentos
+        self.assertEqual(len(gapped), len(joined) + 3200)
+
+
+@rust_required
+class TestFrameSizeValidation(unittest.TestCase):
+    """Pruebas para la validación de tamaño de frame en is_speech."""
+
+    def test_valid_frame_sizes_follow_rate(self) -> None:
+        """Verifica que los tamaños válidos dependen de la tasa configurada."""
+        self.assertEqual(VoiceActivityDetector(sample_rate=16000).valid_frame_sizes(), [160, 320, 480])
+        self.assertEqual(VoiceActivityDetector(sample_rate=8000).valid_frame_sizes(), [80, 160, 240])
+
+    def test_config_and_frame_size_accessors(self) -> None:
+        """Verifica get_config y frame_size_for según la configuración del detector."""
+        # ARRANGE
+        vad = VoiceActivityDetector(aggressiveness=3, sample_rate=32000)
+
+        # ACT & ASSERT
+        self.assertEqual(vad.get_config(), (3, 32000))
+        self.assertEqual(vad.frame_size_for(10), 320)
+        self.assertEqual(vad.frame_size_for(), 960)
+        with self.assertRaises(ValueError):
+            vad.frame_size_for(15)
+
+    def test_invalid_frame_lists_expected_sizes(self) -> None:
+        """Verifica que un frame inválido produce un error con los tamaños esperados."""
+        # ARRANGE
+        vad = VoiceActivityDetector(sample_rate=SAMPLE_RATE)
+        frame = np.zeros(500, dtype=np.int16)
+
+        # ACT & ASSERT
+        with self.assertRaisesRegex(ValueError, "160/320/480"):
+            vad.is_speech(frame)
+
+    def test_analyze_frame_reports_level(self) -> None:
+        """Verifica que analyze_frame devuelve voz, RMS y pico del frame."""
+        # ARRANGE
+        vad = VoiceActivityDetector(aggressiveness=0, sample_rate=SAMPLE_RATE)
+        silence = np.zeros(480, dtype=np.float32)
+        tone = np.full(480, 0.25, dtype=np.float32)
+        tone[10] = -0.8
+
+        # ACT
+        silent_speech, silent_rms, silent_peak = vad.analyze_frame(silence)
+        _, rms, peak = vad.analyze_frame(tone)
+
+        # ASSERT
+        self.assertFalse(silent_speech)
+        self.assertEqual((silent_rms, silent_peak), (0.0, 0.0))
+        self.assertAlmostEqual(peak, 0.8, places=5)
+        self.assertGreater(rms, 0.25)
+        with self.assertRaises(ValueError):
+            vad.analyze_frame(np.zeros(500, dtype=np.float32))
+
+
+@rust_required
+class TestDetectSegmentsMulti(unittest.TestCase):
+    """Pruebas para detect_segments_multi (comparación de agresividad)."""
+
+    def test_returns_labeled_results_in_order(self) -> None:
+        """Verifica que cada nivel solicitado devuelve su propio set de segmentos."""
+        # ARRANGE
+        vad = VoiceActivityDetector(aggressiveness=2, sample_rate=SAMPLE_RATE)
+        audio = _burst_audio([("silencio", 0.5), ("voz", 1.0), ("silencio", 0.5)])
+
+        # ACT
+        results = vad.detect_segments_multi(audio, aggressiveness_levels=[3, 0])
+
+        # ASSERT
+        self.assertEqual([level for level, _ in results], [3, 0])
+        for _, segments in results:
+            self.assertEqual(len(segments), 1)
+
+    def test_invalid_level_raises(self) -> None:
+        """Verifica que un nivel fuera de 0-3 se rechaza antes de procesar."""
+        # ARRANGE
+        vad = VoiceActivityDetector(sample_rate=SAMPLE_RATE)
+        audio = np.zeros(SAMPLE_RATE, dtype=np.float32)
+
+        # ACT & ASSERT
+        with self.assertRaises(ValueError):
+            vad.detect_segments_multi(audio, aggressiveness_levels=[2, 4])
+
+
+def _drain_events(gate, audio: np.ndarray, chunk_samples: int) -> list[tuple[str, int]]:
+    """Empuja `audio` en chunks y devuelve los eventos (nombre, muestras) emitidos."""
+    empty = np.empty(0, dtype=np.float32)
+    events = []
+    for start in range(0, len(audio), chunk_samples):
+        event, utterance = gate.push(audio[start : start + chunk_samples])
+        while event != "continue":
+            events.append((event, 0 if utterance is None else len(utterance)))
+            event, utterance = gate.push(empty)
+    return events
+
+
+@rust_required
+class TestStreamingVadGate(unittest.TestCase):
+    """Pruebas para StreamingVadGate (acumulación y emisión de enunciados)."""
+
+    def test_emits_one_utterance_per_burst(self) -> None:
+        """Verifica el ciclo speech_start -> utterance para cada ráfaga de voz."""
+        # ARRANGE
+        gate = StreamingVadGate(aggressiveness=0, sample_rate=SAMPLE_RATE)
+        audio = _burst_audio([("silencio", 0.5), ("voz", 1.0), ("silencio", 0.5), ("voz", 0.5), ("silencio", 0.5)])
+
+        # ACT: chunks de tamaño no alineado a frame
+        events = _drain_events(gate, audio, 1000)
+
+        # ASSERT
+        self.assertEqual([name for name, _ in events], ["speech_start", "utterance", "speech_start", "utterance"])
+        self.assertGreaterEqual(events[1][1], int(0.9 * SAMPLE_RATE))
+        self.assertLess(events[1][1], int(1.5 * SAMPLE_RATE))
+        self.assertFalse(gate.is_in_speech())
+
+    def test_silence_only_never_emits(self) -> None:
+        """Verifica que el silencio digital no produce eventos."""
+        # ARRANGE
+        gate = StreamingVadGate(sample_rate=SAMPLE_RATE)
+        audio = np.zeros(2 * SAMPLE_RATE, dtype=np.float32)
+
+        # ACT & ASSERT
+        self.assertEqual(_drain_events(gate, audio, 1600), [])
+        self.assertIsNone(gate.flush())
+
+    def test_flush_returns_open_utterance(self) -> None:
+        """Verifica que flush entrega la voz en curso cuando el audio termina sin silencio."""
+        # ARRANGE
+        gate = StreamingVadGate(aggressiveness=0, sample_rate=SAMPLE_RATE)
+        audio = _burst_audio([("silencio", 0.5), ("voz", 1.0)])
+        _drain_events(gate, audio, 1600)
+
+        # ACT
+        utterance = gate.flush()
+
+        # ASSERT
+        self.assertIsNotNone(utterance)
+        self.assertGreaterEqual(len(utterance), int(0.9 * SAMPLE_RATE))
+        self.assertFalse(gate.is_in_speech())
+
+    def test_invalid_frame_ms_raises(self) -> None:
+        """Verifica que frame_ms fuera de 10/20/30 se rechaza."""
+        with self.assertRaises(ValueError):
+            StreamingVadGate(frame_ms=25)
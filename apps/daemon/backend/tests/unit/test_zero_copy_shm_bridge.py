@@ -37,13 +37,14 @@ import pytest
 
 # Intentamos importar el motor Rust
 try:
-    from v2m_engine import SharedAudioBuffer, ZeroCopyAudioRecorder
+    from v2m_engine import SharedAudioBuffer, ZeroCopyAudioRecorder, get_device_capabilities
 
     HAS_RUST_ENGINE = True
 except ImportError:
     HAS_RUST_ENGINE = False
     SharedAudioBuffer = None
     ZeroCopyAudioRecorder = None
+    get_device_capabilities = None
 
 
 # Marker para tests que requieren motor Rust
@@ -81,6 +82,11 @@ class TestSharedAudioBuffer(unittest.TestCase):
         self.assertEqual(buffer.get_capacity(), capacity)
         self.assertEqual(buffer.get_data_len(), 0)  # Vacío inicialmente
 
+    def test_shared_buffer_default_dtype(self) -> None:
+        """Verifica que el dtype por defecto sigue siendo float32."""
+        buffer = SharedAudioBuffer(capacity_samples=1000)
+        self.assertEqual(buffer.get_dtype(), "float32")
+
     def test_shared_buffer_read_as_numpy(self) -> None:
         """Verifica que read_as_numpy devuelve datos correctos."""
         # ARRANGE
@@ -93,6 +99,53 @@ class TestSharedAudioBuffer(unittest.TestCase):
         self.assertEqual(len(data), 0)
         self.assertEqual(data.dtype, np.float32)
 
+    def test_shared_buffer_int16_dtype(self) -> None:
+        """Verifica que dtype='int16' usa 2 bytes por muestra y se reporta."""
+        # ARRANGE & ACT
+        buffer = SharedAudioBuffer(capacity_samples=1000, dtype="int16")
+
+        # ASSERT
+        self.assertEqual(buffer.get_dtype(), "int16")
+        self.assertEqual(buffer.get_capacity(), 1000)
+        self.assertEqual(buffer.read_as_numpy().dtype, np.float32)
+
+        shm = shared_memory.SharedMemory(name=buffer.get_shm_name())
+        self.assertGreaterEqual(shm.size, 1000 * np.dtype(buffer.get_dtype()).itemsize)
+        shm.close()
+
+        with self.assertRaises(ValueError):
+            SharedAudioBuffer(capacity_samples=1000, dtype="float64")
+
+    def test_close_unlinks_segment(self) -> None:
+        """Verifica que close() libera /dev/shm y es idempotente."""
+        # ARRANGE
+        buffer = SharedAudioBuffer(capacity_samples=1000)
+        shm_name = buffer.get_shm_name()
+
+        # ACT
+        buffer.close()
+        buffer.close()
+
+        # ASSERT
+        self.assertTrue(buffer.is_closed())
+        self.assertTrue(buffer.is_finalized())
+        self.assertEqual(buffer.get_data_len(), 0)
+        self.assertEqual(buffer.get_shm_name(), "")
+        self.assertEqual(buffer.get_capacity(), 0)
+        self.assertEqual(len(buffer.read_as_numpy()), 0)
+        with self.assertRaises(FileNotFoundError):
+            shared_memory.SharedMemory(name=shm_name)
+
+    def test_read_range_validates_capacity(self) -> None:
+        """Verifica que read_range rechaza rangos fuera de la capacidad."""
+        # ARRANGE
+        buffer = SharedAudioBuffer(capacity_samples=1000)
+
+        # ACT & ASSERT
+        with self.assertRaises(ValueError):
+            buffer.read_range(900, 200)
+        self.assertEqual(len(buffer.read_range(0, 1000)), 0)  # Nada escrito aún
+
 
 @rust_required
 class TestZeroCopyAudioRecorder(unittest.TestCase):
@@ -120,6 +173,43 @@ class TestZeroCopyAudioRecorder(unittest.TestCase):
         self.assertFalse(recorder.is_recording())
         self.assertEqual(recorder.get_available_samples(), 0)
 
+    def test_buffer_stats_snapshot(self) -> None:
+        """Verifica que get_buffer_stats devuelve una instantánea coherente."""
+        # ARRANGE
+        recorder = ZeroCopyAudioRecorder(sample_rate=16000, max_duration_sec=1)
+
+        # ACT
+        write_pos, capacity, is_finalized, shm_name = recorder.get_buffer_stats()
+
+        # ASSERT
+        self.assertEqual(write_pos, recorder.get_available_samples())
+        self.assertGreater(capacity, 0)
+        self.assertFalse(is_finalized)
+        self.assertEqual(shm_name, recorder.get_shm_name())
+        self.assertEqual(recorder.utilization(), 0.0)
+
+    def test_stream_chunks_rejects_invalid_args(self) -> None:
+        """Verifica que stream_chunks valida argumentos antes de iniciar captura."""
+        # ARRANGE
+        recorder = ZeroCopyAudioRecorder(max_duration_sec=1)
+
+        # ACT & ASSERT
+        with self.assertRaises(ValueError):
+            recorder.stream_chunks(0)
+        with self.assertRaises(ValueError):
+            recorder.stream_chunks(100, on_overrun="skip")
+        self.assertFalse(recorder.is_recording())
+
+
+@rust_required
+class TestDeviceCapabilities(unittest.TestCase):
+    """Pruebas para get_device_capabilities (sin hardware)."""
+
+    def test_unknown_device_raises_os_error(self) -> None:
+        """Verifica que un nombre de dispositivo inexistente lanza OSError."""
+        with self.assertRaises(OSError):
+            get_device_capabilities("v2m-dispositivo-inexistente")
+
 
 class TestAudioRecorderFacadeZeroCopy(unittest.TestCase):
     """Pruebas para el facade AudioRecorder en modo zero_copy."""